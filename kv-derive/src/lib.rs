@@ -0,0 +1,213 @@
+//! `#[derive(KVBody)]`：为由定长整数字段组成的结构体生成`Bytes`、`AttrProxy`和`KVBody`实现。
+//! 字段按声明顺序以小端序拼接，key为字段名。
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+const SUPPORTED_TYPES: &[&str] = &[
+    "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "u128", "i128",
+];
+
+struct Field {
+    name: String,
+    ty: String,
+}
+
+#[proc_macro_derive(KVBody)]
+pub fn derive_kv_body(input: TokenStream) -> TokenStream {
+    match parse_struct(input) {
+        Ok((name, fields)) => expand(&name, &fields),
+        Err(msg) => format!("compile_error!({:?});", msg).parse().unwrap(),
+    }
+}
+
+// 跳过属性和可见性修饰
+fn skip_attrs_and_vis(tokens: &[TokenTree], mut i: usize) -> usize {
+    loop {
+        match tokens.get(i) {
+            Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                i += 2;
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                i += 1;
+                if let Some(TokenTree::Group(g)) = tokens.get(i) {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        i += 1;
+                    }
+                }
+            }
+            _ => return i,
+        }
+    }
+}
+
+fn parse_struct(input: TokenStream) -> Result<(String, Vec<Field>), String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut i = skip_attrs_and_vis(&tokens, 0);
+
+    match tokens.get(i) {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => i += 1,
+        _ => return Err("KVBody can only be derived for structs".into()),
+    }
+    let name = match tokens.get(i) {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("expected struct name".into()),
+    };
+    i += 1;
+
+    let body = match tokens.get(i) {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => g.stream(),
+        Some(TokenTree::Punct(p)) if p.as_char() == '<' => {
+            return Err("KVBody cannot be derived for generic structs".into())
+        }
+        _ => return Err("KVBody can only be derived for structs with named fields".into()),
+    };
+
+    let mut fields = Vec::new();
+    let body: Vec<TokenTree> = body.into_iter().collect();
+    for field in body.split(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ',')) {
+        let j = skip_attrs_and_vis(field, 0);
+        if j >= field.len() {
+            continue;
+        }
+        let name = match (field.get(j), field.get(j + 1)) {
+            (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(p))) if p.as_char() == ':' => {
+                ident.to_string()
+            }
+            _ => return Err("expected named field".into()),
+        };
+        let ty: String = field[j + 2..].iter().map(|t| t.to_string()).collect();
+        if !SUPPORTED_TYPES.contains(&ty.as_str()) {
+            return Err(format!(
+                "field `{}` has unsupported type `{}`, expected one of {:?}",
+                name, ty, SUPPORTED_TYPES
+            ));
+        }
+        fields.push(Field { name, ty });
+    }
+
+    Ok((name, fields))
+}
+
+fn expand(name: &str, fields: &[Field]) -> TokenStream {
+    let p = "::kv_object::__private";
+    let err = "::kv_object::KVObjectError";
+
+    // 各字段的起始偏移，为之前字段长度之和
+    let mut offsets = Vec::new();
+    let mut offset = String::from("0");
+    for field in fields {
+        offsets.push(offset.clone());
+        offset = format!("{} + {}::size_of::<{}>()", offset, p, field.ty);
+    }
+    let total_len = offset;
+
+    let mut from_fields = String::new();
+    let mut to_fields = String::new();
+    let mut get_arms = String::new();
+    let mut set_arms = String::new();
+    let mut len_arms = String::new();
+    let mut keys = String::new();
+    for (field, start) in fields.iter().zip(offsets.iter()) {
+        let (f, t) = (&field.name, &field.ty);
+        from_fields += &format!(
+            "{f}: {{ let start = {start}; let mut buf = [0u8; {p}::size_of::<{t}>()]; \
+             buf.clone_from_slice(&bytes[start..start + {p}::size_of::<{t}>()]); \
+             {t}::from_le_bytes(buf) }},",
+            f = f,
+            t = t,
+            p = p,
+            start = start
+        );
+        to_fields += &format!("ret.extend_from_slice(&self.{}.to_le_bytes()[..]);", f);
+        get_arms += &format!(
+            "{:?} => Ok({}::Vec::<u8>::from(&self.{}.to_le_bytes()[..])),",
+            f, p, f
+        );
+        set_arms += &format!(
+            "{fs:?} => {{ let mut buf = [0u8; {p}::size_of::<{t}>()]; \
+             if value.len() != buf.len() {{ \
+             return Err({err}::ValueLength {{ expected: buf.len(), got: value.len() }}); }} \
+             buf.clone_from_slice(value); self.{f} = {t}::from_le_bytes(buf); }}",
+            fs = f,
+            f = f,
+            t = t,
+            p = p,
+            err = err
+        );
+        len_arms += &format!("{:?} => Ok({}::size_of::<{}>()),", f, p, t);
+        keys += &format!("{:?},", f);
+    }
+
+    let code = format!(
+        r#"
+impl {p}::Bytes for {name} {{
+    type BytesType = {p}::Vec<u8>;
+
+    type Error = {err};
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {{
+        if bytes.len() != {total_len} {{
+            return Err({err}::DeSerializeError);
+        }}
+        Ok(Self {{ {from_fields} }})
+    }}
+
+    fn to_bytes(&self) -> Self::BytesType {{
+        let mut ret = {p}::Vec::<u8>::new();
+        {to_fields}
+        ret
+    }}
+}}
+
+impl ::kv_object::prelude::AttrProxy for {name} {{
+    type Byte = {p}::Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, {err}> {{
+        match key {{
+            {get_arms}
+            _ => Err({err}::KeyIndexError),
+        }}
+    }}
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), {err}> {{
+        match key {{
+            {set_arms}
+            _ => return Err({err}::KeyIndexError),
+        }}
+        Ok(())
+    }}
+
+    fn list_keys(&self) -> &'static [&'static str] {{
+        &[{keys}]
+    }}
+
+    fn key_len(&self, key: &str) -> Result<usize, {err}> {{
+        match key {{
+            {len_arms}
+            _ => Err({err}::KeyIndexError),
+        }}
+    }}
+}}
+
+impl ::kv_object::kv_object::KVBody for {name} {{
+    fn size_hint() -> Option<usize> {{
+        Some({total_len})
+    }}
+}}
+"#,
+        p = p,
+        err = err,
+        name = name,
+        total_len = total_len,
+        from_fields = from_fields,
+        to_fields = to_fields,
+        get_arms = get_arms,
+        set_arms = set_arms,
+        len_arms = len_arms,
+        keys = keys,
+    );
+
+    code.parse().unwrap()
+}
@@ -0,0 +1,118 @@
+//! 结构体实现`AttrProxy`时读写整数字段的辅助函数。
+//! 字节序由结构体自行选择，同一字段的`get_key`和`set_key`必须使用相同的字节序。
+
+use crate::KVObjectError;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+macro_rules! int_helpers {
+    ($t:ty, $read_le:ident, $read_be:ident, $write_le:ident, $write_be:ident) => {
+        pub fn $read_le(value: &[u8]) -> Result<$t, KVObjectError> {
+            if value.len() != size_of::<$t>() {
+                return Err(KVObjectError::ValueLength {
+                    expected: size_of::<$t>(),
+                    got: value.len(),
+                });
+            }
+            let mut bytes = [0u8; size_of::<$t>()];
+            bytes.clone_from_slice(value);
+            Ok(<$t>::from_le_bytes(bytes))
+        }
+
+        pub fn $read_be(value: &[u8]) -> Result<$t, KVObjectError> {
+            if value.len() != size_of::<$t>() {
+                return Err(KVObjectError::ValueLength {
+                    expected: size_of::<$t>(),
+                    got: value.len(),
+                });
+            }
+            let mut bytes = [0u8; size_of::<$t>()];
+            bytes.clone_from_slice(value);
+            Ok(<$t>::from_be_bytes(bytes))
+        }
+
+        pub fn $write_le(value: $t) -> Vec<u8> {
+            Vec::<u8>::from(value.to_le_bytes().as_ref())
+        }
+
+        pub fn $write_be(value: $t) -> Vec<u8> {
+            Vec::<u8>::from(value.to_be_bytes().as_ref())
+        }
+    };
+}
+
+int_helpers!(u16, read_u16_le, read_u16_be, write_u16_le, write_u16_be);
+int_helpers!(i16, read_i16_le, read_i16_be, write_i16_le, write_i16_be);
+int_helpers!(u32, read_u32_le, read_u32_be, write_u32_le, write_u32_be);
+int_helpers!(i32, read_i32_le, read_i32_be, write_i32_le, write_i32_be);
+int_helpers!(u64, read_u64_le, read_u64_be, write_u64_le, write_u64_be);
+int_helpers!(i64, read_i64_le, read_i64_be, write_i64_le, write_i64_be);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::AttrProxy;
+
+    // x使用小端序，y使用大端序
+    struct MixedPoint {
+        x: i32,
+        y: i32,
+    }
+
+    impl AttrProxy for MixedPoint {
+        type Byte = Vec<u8>;
+
+        fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+            match key {
+                "x" => Ok(write_i32_le(self.x)),
+                "y" => Ok(write_i32_be(self.y)),
+                _ => Err(KVObjectError::KeyIndexError),
+            }
+        }
+
+        fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+            match key {
+                "x" => self.x = read_i32_le(value)?,
+                "y" => self.y = read_i32_be(value)?,
+                _ => return Err(KVObjectError::KeyIndexError),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_mixed_endian() {
+        let mut point = MixedPoint { x: 1, y: 2 };
+
+        assert_eq!(point.get_key("x").unwrap(), [1, 0, 0, 0]);
+        assert_eq!(point.get_key("y").unwrap(), [0, 0, 0, 2]);
+
+        point
+            .set_key("x", &Vec::from([0, 1, 0, 0].as_ref()))
+            .unwrap();
+        point
+            .set_key("y", &Vec::from([0, 0, 1, 0].as_ref()))
+            .unwrap();
+        assert_eq!(point.x, 256);
+        assert_eq!(point.y, 256);
+
+        assert_eq!(
+            point.set_key("y", &Vec::from([0, 1].as_ref())).unwrap_err(),
+            KVObjectError::ValueLength {
+                expected: 4,
+                got: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        assert_eq!(
+            read_u64_be(&write_u64_be(0x0102_0304)).unwrap(),
+            0x0102_0304
+        );
+        assert_eq!(read_i16_le(&write_i16_le(-2)).unwrap(), -2);
+        assert_eq!(write_u16_be(0x0102), [0x01, 0x02]);
+        assert_eq!(write_u16_le(0x0102), [0x02, 0x01]);
+    }
+}
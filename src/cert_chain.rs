@@ -0,0 +1,80 @@
+//! 证书链：根证书依次签发下级证书，末端证书用于签名对象
+
+use crate::kv_object::DefaultHasher;
+use crate::sm2::{CertificateSm2, KeyPairSm2, SignatureSm2};
+use crate::KVObjectError;
+use alloc::vec::Vec;
+use asymmetric_crypto::prelude::{Certificate, Keypair};
+use dislog_hal::Bytes;
+use rand::RngCore;
+
+// 链上的一级：下级证书及上级对其压缩编码的签名
+#[derive(Debug, Clone)]
+pub struct CertLink {
+    pub cert: CertificateSm2,
+    pub signature: SignatureSm2,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CertChain {
+    links: Vec<CertLink>,
+}
+
+impl CertChain {
+    pub fn links(&self) -> &[CertLink] {
+        &self.links
+    }
+
+    // 末端证书，空链返回None
+    pub fn leaf(&self) -> Option<&CertificateSm2> {
+        self.links.last().map(|link| &link.cert)
+    }
+
+    // 从根证书开始逐级验证签名，成功后返回末端证书
+    pub fn verify(&self, root: &CertificateSm2) -> Result<&CertificateSm2, KVObjectError> {
+        let mut issuer = root;
+        for link in self.links.iter() {
+            if !issuer.verify::<DefaultHasher>(link.cert.to_bytes().as_ref(), &link.signature) {
+                return Err(KVObjectError::KVHeadVerifyError);
+            }
+            issuer = &link.cert;
+        }
+        self.leaf().ok_or(KVObjectError::UnsignedObject)
+    }
+}
+
+// 逐级构造证书链，每一级由上一级的密钥对签发
+#[derive(Debug, Default)]
+pub struct CertChainBuilder {
+    links: Vec<CertLink>,
+}
+
+impl CertChainBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 由parent签发child的证书，parent须为根或上一级的密钥对，否则返回UntrustedIssuer
+    pub fn push_signed_by(
+        mut self,
+        child: &KeyPairSm2,
+        parent: &KeyPairSm2,
+        rng: &mut impl RngCore,
+    ) -> Result<Self, KVObjectError> {
+        if let Some(last) = self.links.last() {
+            if last.cert != parent.get_certificate() {
+                return Err(KVObjectError::UntrustedIssuer);
+            }
+        }
+        let cert = child.get_certificate();
+        let signature = parent
+            .sign::<DefaultHasher, _>(cert.to_bytes().as_ref(), rng)
+            .map_err(|_| KVObjectError::SerializeSignError)?;
+        self.links.push(CertLink { cert, signature });
+        Ok(self)
+    }
+
+    pub fn build(self) -> CertChain {
+        CertChain { links: self.links }
+    }
+}
@@ -0,0 +1,60 @@
+//! 结构体T数据的编码方式，配合KVObjectGeneric的类型参数C使用
+
+use crate::kv_object::MAX_FRAME_LEN;
+use crate::prelude::BodyCodec;
+use crate::KVObjectError;
+use alloc::vec::Vec;
+
+// 解压后结构体T数据的长度上限，防止高压缩比的数据在验签前耗尽内存
+pub const MAX_BODY_LEN: usize = MAX_FRAME_LEN;
+
+// 不做任何变换，序列化格式与未引入编码时相同
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoCompression;
+
+impl BodyCodec for NoCompression {
+    const IDENTITY: bool = true;
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        Vec::<u8>::from(data)
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, KVObjectError> {
+        Ok(Vec::<u8>::from(data))
+    }
+}
+
+// 基于flate2的deflate压缩
+#[cfg(feature = "deflate")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Deflate;
+
+#[cfg(feature = "deflate")]
+impl BodyCodec for Deflate {
+    fn compress(data: &[u8]) -> Vec<u8> {
+        use flate2::write::DeflateEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        // 写入Vec不会失败
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, KVObjectError> {
+        use flate2::read::DeflateDecoder;
+        use std::io::Read;
+
+        // 多读一个字节以区分恰好达到上限与超出上限
+        let mut ret = Vec::new();
+        DeflateDecoder::new(data)
+            .take(MAX_BODY_LEN as u64 + 1)
+            .read_to_end(&mut ret)
+            .map_err(|_| KVObjectError::DeSerializeError)?;
+        if ret.len() > MAX_BODY_LEN {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        Ok(ret)
+    }
+}
@@ -0,0 +1,168 @@
+//! KVObject的JSON信封格式：`{"type":"...","cert":"...","signature":"...","body":"..."}`。
+//! 字段值只会是不含转义字符的字符串或null，此处只实现该子集的读写，不依赖serde，body的base64编码由base64库完成。
+
+use crate::KVObjectError;
+use alloc::string::String;
+use alloc::vec::Vec;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+// 标准base64编码，带=填充
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    STANDARD.encode(data)
+}
+
+// 解码标准base64，填充须完整且末尾字符的未用位须为零，保证每个body只有一种编码
+pub(crate) fn base64_decode(s: &str) -> Result<Vec<u8>, KVObjectError> {
+    STANDARD
+        .decode(s)
+        .map_err(|_| KVObjectError::DeSerializeError)
+}
+
+// 按给定顺序写出JSON对象，None写为null
+pub(crate) fn write_object(fields: &[(&str, Option<&str>)]) -> String {
+    let mut ret = String::from("{");
+    for (i, (key, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            ret.push(',');
+        }
+        ret.push('"');
+        ret.push_str(key);
+        ret.push_str("\":");
+        match value {
+            Some(value) => {
+                ret.push('"');
+                ret.push_str(value);
+                ret.push('"');
+            }
+            None => ret.push_str("null"),
+        }
+    }
+    ret.push('}');
+    ret
+}
+
+fn skip_ws(s: &[u8], mut i: usize) -> usize {
+    while i < s.len() && matches!(s[i], b' ' | b'\t' | b'\n' | b'\r') {
+        i += 1;
+    }
+    i
+}
+
+fn expect(s: &[u8], i: usize, c: u8) -> Result<usize, KVObjectError> {
+    let i = skip_ws(s, i);
+    if s.get(i) != Some(&c) {
+        return Err(KVObjectError::DeSerializeError);
+    }
+    Ok(i + 1)
+}
+
+// 读取不含转义字符的字符串，i指向起始引号
+fn read_str(s: &str, i: usize) -> Result<(&str, usize), KVObjectError> {
+    let bytes = s.as_bytes();
+    let start = expect(bytes, i, b'"')?;
+    let len = bytes[start..]
+        .iter()
+        .position(|c| *c == b'"')
+        .ok_or(KVObjectError::DeSerializeError)?;
+    let value = &s[start..start + len];
+    if value.contains('\\') {
+        return Err(KVObjectError::DeSerializeError);
+    }
+    Ok((value, start + len + 1))
+}
+
+// 解析值均为字符串或null的扁平JSON对象，返回各字段
+pub(crate) fn read_object(s: &str) -> Result<Vec<(&str, Option<&str>)>, KVObjectError> {
+    let bytes = s.as_bytes();
+    let mut ret = Vec::new();
+    let mut i = expect(bytes, 0, b'{')?;
+    if bytes.get(skip_ws(bytes, i)) == Some(&b'}') {
+        i = skip_ws(bytes, i) + 1;
+    } else {
+        loop {
+            let (key, next) = read_str(s, i)?;
+            i = expect(bytes, next, b':')?;
+            i = skip_ws(bytes, i);
+            let value = if bytes[i..].starts_with(b"null") {
+                i += 4;
+                None
+            } else {
+                let (value, next) = read_str(s, i)?;
+                i = next;
+                Some(value)
+            };
+            if ret.iter().any(|(k, _)| *k == key) {
+                return Err(KVObjectError::DeSerializeError);
+            }
+            ret.push((key, value));
+
+            i = skip_ws(bytes, i);
+            match bytes.get(i) {
+                Some(b',') => i += 1,
+                Some(b'}') => {
+                    i += 1;
+                    break;
+                }
+                _ => return Err(KVObjectError::DeSerializeError),
+            }
+        }
+    }
+    if skip_ws(bytes, i) != bytes.len() {
+        return Err(KVObjectError::DeSerializeError);
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        let cases: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+        for (data, encoded) in cases.iter() {
+            assert_eq!(base64_encode(data), *encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), *data);
+        }
+        assert!(base64_decode("Zm9").is_err());
+        assert!(base64_decode("Zg==Zm9v").is_err());
+        assert!(base64_decode("Zm9*").is_err());
+
+        // 填充缺失、多余或位置错误
+        assert!(base64_decode("Zg").is_err());
+        assert!(base64_decode("Zg=").is_err());
+        assert!(base64_decode("Zm8==").is_err());
+        assert!(base64_decode("Zm9v=").is_err());
+        assert!(base64_decode("Z===").is_err());
+        assert!(base64_decode("Zg=a").is_err());
+        // 末尾字符的未用位不为零
+        assert!(base64_decode("Zh==").is_err());
+        assert!(base64_decode("Zm9=").is_err());
+    }
+
+    #[test]
+    fn test_object() {
+        let s = write_object(&[("type", Some("Transaction")), ("cert", None)]);
+        assert_eq!(s, r#"{"type":"Transaction","cert":null}"#);
+        assert_eq!(
+            read_object(&s).unwrap(),
+            [("type", Some("Transaction")), ("cert", None)]
+        );
+        assert_eq!(
+            read_object(" { \"a\" : \"b\" } ").unwrap(),
+            [("a", Some("b"))]
+        );
+        assert!(read_object("{}").unwrap().is_empty());
+        assert!(read_object(r#"{"a":"b","a":"c"}"#).is_err());
+        assert!(read_object(r#"{"a":"b\"c"}"#).is_err());
+        assert!(read_object(r#"{"a":1}"#).is_err());
+        assert!(read_object(r#"{"a":"b"} x"#).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+//! 按密钥标识查找签发者证书，多个对象共用签发者时以8字节标识代替33字节证书
+
+use crate::sm2::CertificateSm2;
+use alloc::collections::BTreeMap;
+use asymmetric_crypto::hasher::sm3::Sm3;
+use dislog_hal::{Bytes, Hasher};
+
+pub const KEY_ID_LEN: usize = 8;
+
+// 证书压缩编码的SM3摘要的前8字节，固定使用SM3以保证标识与编译选项无关
+pub fn key_id(cert: &CertificateSm2) -> [u8; KEY_ID_LEN] {
+    let mut hasher = Sm3::default();
+    hasher.update(cert.to_bytes().as_ref());
+    let digest = hasher.finalize();
+    let mut ret = [0u8; KEY_ID_LEN];
+    ret.clone_from_slice(&digest[..KEY_ID_LEN]);
+    ret
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KeyRegistry {
+    certs: BTreeMap<[u8; KEY_ID_LEN], CertificateSm2>,
+}
+
+impl KeyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 登记证书，返回其密钥标识
+    pub fn insert(&mut self, cert: CertificateSm2) -> [u8; KEY_ID_LEN] {
+        let id = key_id(&cert);
+        self.certs.insert(id, cert);
+        id
+    }
+
+    pub fn get(&self, id: &[u8; KEY_ID_LEN]) -> Option<&CertificateSm2> {
+        self.certs.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.certs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.certs.is_empty()
+    }
+}
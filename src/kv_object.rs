@@ -1,29 +1,93 @@
-use crate::prelude::{AttrProxy, KValueObject};
-use crate::sm2::{CertificateSm2, KeyPairSm2};
+use crate::codec::NoCompression;
+use crate::envelope;
+use crate::key_registry::{key_id, KeyRegistry, KEY_ID_LEN};
+use crate::metrics::ParseTimer;
+use crate::prelude::{AttrProxy, BodyCodec, KVKeypair, KValueObject};
+use crate::sm2::{CertificateSm2, KeyPairSm2, SignatureSm2, Sm2Context};
 use crate::KVObjectError;
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
 use alloc::vec::Vec;
+use asymmetric_crypto::hasher::sha3::Sha3;
 use asymmetric_crypto::hasher::sm3::Sm3;
-use asymmetric_crypto::prelude::{Certificate, Keypair};
-use core::fmt::Debug;
-use dislog_hal::Bytes;
+use asymmetric_crypto::prelude::Certificate;
+use core::convert::TryFrom;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use dislog_hal::{Bytes, Hasher};
+use hex::{FromHex, ToHex};
 use rand::RngCore;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub const MSGTYPE_LEN: usize = 1;
 pub const MSGTYPE_OFFSET: usize = 0;
 pub const MSGTYPE_END: usize = MSGTYPE_OFFSET + MSGTYPE_LEN;
 
-pub const CERT_LEN: usize = 33;
-pub const CERT_OFFSET: usize = MSGTYPE_END;
+pub const FLAGS_LEN: usize = 1;
+pub const FLAGS_OFFSET: usize = MSGTYPE_END;
+pub const FLAGS_END: usize = FLAGS_OFFSET + FLAGS_LEN;
+
+// 标志位：已签名，证书和签名字段有效
+pub const FLAG_SIGNED: u8 = 0x01;
+
+// 标志位：头部省略证书，证书由接收方根据上下文提供
+pub const FLAG_NO_CERT: u8 = 0x02;
+
+// 结构体T的数据经过压缩
+pub const FLAG_COMPRESSED: u8 = 0x04;
+
+// 结构体T的数据之后附带不参与签名的元数据
+pub const FLAG_METADATA: u8 = 0x08;
+
+// 格式版本，写入标志位字节的高4位。版本1起签名原文带有长度前缀，不兼容版本0
+pub const FORMAT_VERSION: u8 = 1;
+pub const VERSION_SHIFT: u32 = 4;
+
+// 低4位均已定义为标志位，高4位为版本号，不存在未定义的位，版本检查即拒绝其余取值
+// 新增标志位时须挤占版本号的位，此断言提醒同时修改check_flags
+const _: () = assert!(
+    FLAG_SIGNED | FLAG_NO_CERT | FLAG_COMPRESSED | FLAG_METADATA == (1 << VERSION_SHIFT) - 1
+);
+
+// 拒绝版本不为FORMAT_VERSION的标志位字节
+fn check_flags(flags: u8) -> Result<(), KVObjectError> {
+    if flags >> VERSION_SHIFT != FORMAT_VERSION {
+        return Err(KVObjectError::DeSerializeError);
+    }
+    Ok(())
+}
+
+// 以下为SM2密钥对的头部布局，其他密钥对的证书和签名长度由KVKeypair给出
+pub const CERT_LEN: usize = <KeyPairSm2 as KVKeypair>::CERT_LEN;
+pub const CERT_OFFSET: usize = FLAGS_END;
 pub const CERT_END: usize = CERT_OFFSET + CERT_LEN;
 
-pub const SIGTURE_LEN: usize = 64;
+pub const SIGTURE_LEN: usize = <KeyPairSm2 as KVKeypair>::SIGTURE_LEN;
 pub const SIGTURE_OFFSET: usize = CERT_END;
 pub const SIGTURE_END: usize = SIGTURE_OFFSET + SIGTURE_LEN;
 
-pub const HEAD_TOTAL_LEN: usize = MSGTYPE_LEN + CERT_LEN + SIGTURE_LEN;
+pub const CRC_LEN: usize = 4;
+
+// 流式传输时每个对象前的长度前缀（u32小端序）
+pub const FRAME_LEN_LEN: usize = 4;
+
+// 单个帧（不含长度前缀）的默认长度上限，防止伪造的长度前缀使解码器无限缓冲
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 文件存储时置于对象之前的魔数，不属于签名范围
+pub const MAGIC: [u8; 4] = *b"KVOB";
+
+pub const HEAD_TOTAL_LEN: usize = MSGTYPE_LEN + FLAGS_LEN + CERT_LEN + SIGTURE_LEN;
+
+// 编译期校验头部各字段首尾相接，且总长度与各字段之和一致
+const _: () = assert!(MSGTYPE_OFFSET == 0);
+const _: () = assert!(FLAGS_OFFSET == MSGTYPE_END);
+const _: () = assert!(CERT_OFFSET == FLAGS_END);
+const _: () = assert!(SIGTURE_OFFSET == CERT_END);
+const _: () = assert!(SIGTURE_END == HEAD_TOTAL_LEN);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MsgType {
     IssueQuotaRequest,
     QuotaControlField,
@@ -33,6 +97,67 @@ pub enum MsgType {
     Transaction,
 }
 
+impl MsgType {
+    // 全部消息类型，按编码顺序排列
+    pub fn all() -> &'static [MsgType] {
+        &[
+            MsgType::IssueQuotaRequest,
+            MsgType::QuotaControlField,
+            MsgType::DigitalCurrency,
+            MsgType::QuotaRecycleReceipt,
+            MsgType::ConvertQoutaRequest,
+            MsgType::Transaction,
+        ]
+    }
+
+    // 序列化时写入的类型编码
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            MsgType::IssueQuotaRequest => 0x01,
+            MsgType::QuotaControlField => 0x02,
+            MsgType::DigitalCurrency => 0x03,
+            MsgType::QuotaRecycleReceipt => 0x04,
+            MsgType::ConvertQoutaRequest => 0x05,
+            MsgType::Transaction => 0x06,
+        }
+    }
+
+    // 稳定的类型名称，用于日志和界面展示
+    pub fn name(&self) -> &'static str {
+        match self {
+            MsgType::IssueQuotaRequest => "IssueQuotaRequest",
+            MsgType::QuotaControlField => "QuotaControlField",
+            MsgType::DigitalCurrency => "DigitalCurrency",
+            MsgType::QuotaRecycleReceipt => "QuotaRecycleReceipt",
+            MsgType::ConvertQoutaRequest => "ConvertQoutaRequest",
+            MsgType::Transaction => "Transaction",
+        }
+    }
+
+    // 由name()给出的名称查找消息类型
+    pub fn from_name(name: &str) -> Option<MsgType> {
+        MsgType::all().iter().find(|t| t.name() == name).cloned()
+    }
+
+    // 额度发行和额度转换请求
+    pub fn is_request(&self) -> bool {
+        matches!(
+            self,
+            MsgType::IssueQuotaRequest | MsgType::ConvertQoutaRequest
+        )
+    }
+
+    // 额度回收回执
+    pub fn is_receipt(&self) -> bool {
+        matches!(self, MsgType::QuotaRecycleReceipt)
+    }
+
+    // 数字货币
+    pub fn is_currency(&self) -> bool {
+        matches!(self, MsgType::DigitalCurrency)
+    }
+}
+
 impl Bytes for MsgType {
     type BytesType = Vec<u8>;
 
@@ -54,17 +179,38 @@ impl Bytes for MsgType {
     }
 
     fn to_bytes(&self) -> Self::BytesType {
-        Vec::<u8>::from(
-            match self {
-                MsgType::IssueQuotaRequest => [0x01],
-                MsgType::QuotaControlField => [0x02],
-                MsgType::DigitalCurrency => [0x03],
-                MsgType::QuotaRecycleReceipt => [0x04],
-                MsgType::ConvertQoutaRequest => [0x05],
-                MsgType::Transaction => [0x06],
-            }
-            .as_ref(),
-        )
+        Vec::<u8>::from([self.as_u8()].as_ref())
+    }
+}
+
+// 可读格式（如JSON）中序列化为name()给出的名称，二进制格式中序列化为as_u8()给出的编码
+impl Serialize for MsgType {
+    fn serialize<SE>(&self, serializer: SE) -> Result<SE::Ok, SE::Error>
+    where
+        SE: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.name())
+        } else {
+            serializer.serialize_u8(self.as_u8())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MsgType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let name = String::deserialize(deserializer)?;
+            MsgType::from_name(&name)
+                .ok_or_else(|| serde::de::Error::custom(format_args!("unknown message type")))
+        } else {
+            let code = u8::deserialize(deserializer)?;
+            MsgType::from_bytes(&[code])
+                .map_err(|_| serde::de::Error::custom(format_args!("unknown message type")))
+        }
     }
 }
 
@@ -77,155 +223,1647 @@ pub fn get_msgtpye(data: &[u8]) -> Result<MsgType, KVObjectError> {
         .map_err(|_| KVObjectError::FindTypeError)
 }
 
+// 读取消息类型前先校验头部完整，避免从截断的数据中取类型
+pub fn peek_msg_type(bytes: &[u8]) -> Result<MsgType, KVObjectError> {
+    if bytes.len() < HEAD_TOTAL_LEN {
+        return Err(KVObjectError::DeSerializeError);
+    }
+
+    MsgType::from_bytes(&bytes[MSGTYPE_OFFSET..MSGTYPE_END])
+}
+
+// 读取长度前缀，返回含前缀的帧长度，超过max_frame_len时返回错误
+fn frame_end(bytes: &[u8], max_frame_len: usize) -> Result<usize, KVObjectError> {
+    let mut len = [0u8; FRAME_LEN_LEN];
+    len.clone_from_slice(&bytes[..FRAME_LEN_LEN]);
+    let len = u32::from_le_bytes(len) as usize;
+    if len > max_frame_len {
+        return Err(KVObjectError::DeSerializeError);
+    }
+    FRAME_LEN_LEN
+        .checked_add(len)
+        .ok_or(KVObjectError::DeSerializeError)
+}
+
+// 统计to_frame生成的连续帧的个数，只读取长度前缀，不解析对象，末尾帧不完整时返回错误
+// 长度前缀超过MAX_FRAME_LEN的帧视为错误
+pub fn count_framed(bytes: &[u8]) -> Result<usize, KVObjectError> {
+    let mut count = 0;
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        if rest.len() < FRAME_LEN_LEN {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let end = frame_end(rest, MAX_FRAME_LEN)?;
+        if rest.len() < end {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        rest = &rest[end..];
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(feature = "derive")]
+pub use kv_derive::KVBody;
+
+// 结构体T的Bytes::Error可以是自定义类型，只需能转换为KVObjectError
 pub trait KVBody:
     Debug
     + Clone
     + Serialize
     + for<'de> Deserialize<'de>
-    + Bytes<Error = KVObjectError>
+    + Bytes<Error: Into<KVObjectError>>
     + AttrProxy<Byte = Vec<u8>>
 {
+    // 通过From构造对象时使用的消息类型
+    const DEFAULT_MSG_TYPE: MsgType = MsgType::IssueQuotaRequest;
+
+    // 结构体T序列化后的固定长度，变长结构返回None
+    fn size_hint() -> Option<usize> {
+        None
+    }
+
+    // 签名覆盖的key，其余key在签名后仍可修改。返回空表示签名覆盖整个结构体T
+    // 非空时以此为准：签名原文仅由各key的值组成，不经C编码也不使用canonical_bytes，
+    // 其余字节（包括解析时保留的原始数据）不受签名保护
+    fn signed_keys() -> &'static [&'static str] {
+        &[]
+    }
+
+    // 校验结构体T的业务约束，默认不做限制
+    fn validate(&self) -> Result<(), KVObjectError> {
+        Ok(())
+    }
+
+    // 对象的签发时间戳，没有时间戳字段的结构返回None
+    fn timestamp(&self) -> Option<u64> {
+        None
+    }
+
+    // 结构体T序列化后的长度，实现方可不经序列化直接给出
+    fn byte_len(&self) -> usize {
+        match Self::size_hint() {
+            Some(size) => size,
+            None => self.to_bytes().as_ref().len(),
+        }
+    }
+
+    // 按以.分隔的路径读取嵌套字段，默认将整个路径视为一个key
+    fn get_path(&self, path: &str) -> Result<Vec<u8>, KVObjectError> {
+        self.get_key(path)
+    }
+
+    // 按以.分隔的路径写入嵌套字段，默认将整个路径视为一个key
+    fn set_path(&mut self, path: &str, value: &[u8]) -> Result<(), KVObjectError> {
+        self.set_key(path, &Vec::<u8>::from(value))
+    }
+
+    // 结构体T自身声明的消息类型，没有类型字段的结构返回None
+    fn declared_type(&self) -> Option<MsgType> {
+        None
+    }
+
+    // 序列化、签名和验签使用的确定性编码，默认与to_bytes相同，长度须与to_bytes一致
+    // 含映射等字段、同一内容可能有多种字节顺序的结构必须实现此方法
+    // 声明了signed_keys时签名原文不使用此方法
+    fn canonical_bytes(&self) -> Vec<u8> {
+        Vec::<u8>::from(self.to_bytes().as_ref())
+    }
+
+    // key对应值的字节宽度，set_key据此校验长度，默认与AttrProxy::key_len相同
+    // 宽度未知时返回错误，由结构体T自行校验
+    fn key_width(&self, key: &str) -> Result<usize, KVObjectError> {
+        self.key_len(key)
+    }
+
+    // 将旧的key名映射为当前key名，字段改名后旧名仍可读写
+    fn key_alias(key: &str) -> &str {
+        key
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KVObject<T: KVBody> {
+// 元数据，依次为各项的key和值
+type Metadata = Vec<(String, Vec<u8>)>;
+
+// 未指定摘要算法时签名和验签使用的算法，默认为Sm3，启用default-hasher-sha3后为Sha3
+#[cfg(not(feature = "default-hasher-sha3"))]
+pub type DefaultHasher = Sm3;
+
+#[cfg(feature = "default-hasher-sha3")]
+pub type DefaultHasher = Sha3;
+
+// K为签名使用的密钥对，H为签名和验签使用的摘要算法，默认为DefaultHasher
+// C为结构体T数据的编码方式，默认不压缩
+#[derive(Serialize, Deserialize)]
+pub struct KVObjectGeneric<T: KVBody, K: KVKeypair, H = DefaultHasher, C = NoCompression> {
     msg_type: MsgType,
-    cert: Option<CertificateSm2>,
-    signature: Option<<CertificateSm2 as Certificate>::Signature>,
+    cert: Option<K::Cert>,
+    signature: Option<K::Sig>,
     #[serde(bound(deserialize = "T: KVBody"))]
     t_obj: T,
+    // from_bytes时保留的原始结构体数据，T无法无损往返时仍能原样输出，修改key后清除
+    #[serde(skip)]
+    raw_body: Option<Vec<u8>>,
+    // 不参与签名的元数据，由转发节点附加
+    #[serde(skip)]
+    metadata: Metadata,
+    // 通过body_mut借出过可变引用，签名可能已与结构体T不符，重新签名后清除
+    #[serde(skip)]
+    stale: bool,
+    #[serde(skip)]
+    marker: PhantomData<fn() -> (H, C)>,
+}
+
+pub type KVObject<T, H = DefaultHasher> = KVObjectGeneric<T, KeyPairSm2, H>;
+
+pub type KVObjectSha3<T> = KVObject<T, Sha3>;
+
+#[cfg(feature = "deflate")]
+pub type KVObjectDeflate<T> = KVObjectGeneric<T, KeyPairSm2, DefaultHasher, crate::codec::Deflate>;
+
+impl<T: KVBody, K: KVKeypair, H, C> Debug for KVObjectGeneric<T, K, H, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KVObject")
+            .field("msg_type", &self.msg_type)
+            .field("cert", &self.cert)
+            .field("signature", &self.signature)
+            .field("t_obj", &self.t_obj)
+            .field("raw_body", &self.raw_body)
+            .field("metadata", &self.metadata)
+            .field("stale", &self.stale)
+            .finish()
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H, C> Clone for KVObjectGeneric<T, K, H, C> {
+    fn clone(&self) -> Self {
+        Self {
+            msg_type: self.msg_type.clone(),
+            cert: self.cert.clone(),
+            signature: self.signature.clone(),
+            t_obj: self.t_obj.clone(),
+            raw_body: self.raw_body.clone(),
+            metadata: self.metadata.clone(),
+            stale: self.stale,
+            marker: PhantomData,
+        }
+    }
 }
 
-impl<T: KVBody> KVObject<T> {
+impl<T: KVBody, K: KVKeypair, H: Default + Hasher<Output = [u8; 32]>, C: BodyCodec>
+    KVObjectGeneric<T, K, H, C>
+{
     pub fn new(msg_type: MsgType, t_obj: T) -> Self {
         Self {
             msg_type,
             cert: None,
             signature: None,
             t_obj,
+            raw_body: None,
+            metadata: Vec::new(),
+            stale: false,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn from_parts(
+        msg_type: MsgType,
+        cert: Option<K::Cert>,
+        signature: Option<K::Sig>,
+        t_obj: T,
+    ) -> Self {
+        Self {
+            msg_type,
+            cert,
+            signature,
+            t_obj,
+            raw_body: None,
+            metadata: Vec::new(),
+            stale: false,
+            marker: PhantomData,
+        }
+    }
+
+    // 由单独收到的结构体T序列化数据和签名组装对象，组装后可调用verfiy_kvhead验证
+    pub fn assemble(
+        msg_type: MsgType,
+        body_bytes: &[u8],
+        cert: K::Cert,
+        sig: K::Sig,
+    ) -> Result<Self, KVObjectError> {
+        let t_obj = T::from_bytes(body_bytes).map_err(Into::into)?;
+
+        Ok(Self::from_parts(msg_type, Some(cert), Some(sig), t_obj))
+    }
+
+    // 复制消息类型和结构体T，清空证书和签名，用于转交其他签名者
+    pub fn clone_unsigned(&self) -> Self {
+        Self {
+            msg_type: self.msg_type.clone(),
+            cert: None,
+            signature: None,
+            t_obj: self.t_obj.clone(),
+            raw_body: self.raw_body.clone(),
+            metadata: self.metadata.clone(),
+            stale: false,
+            marker: PhantomData,
+        }
+    }
+
+    // 验签并给出具体的失败原因
+    pub fn verify_detailed(&self) -> Result<(), VerifyFailure> {
+        self.verify_detailed_by(|body, cert, signature| {
+            verify_signature::<K, H>(body, cert, signature)
+        })
+    }
+
+    // verify_detailed的检查顺序，最后由verify验证签名原文，各验签入口共用以保证错误一致
+    fn verify_detailed_by(
+        &self,
+        verify: impl FnOnce(&[u8], &K::Cert, &K::Sig) -> Result<(), VerifyFailure>,
+    ) -> Result<(), VerifyFailure> {
+        // 根据证书链验证证书，略过
+        // 根据证书验证签名
+        let cert = self.cert.as_ref().ok_or(VerifyFailure::MissingCert)?;
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(VerifyFailure::MissingSignature)?;
+        if self.stale {
+            return Err(VerifyFailure::StaleSignature);
         }
+        let body = self.signing_bytes().map_err(VerifyFailure::InvalidBody)?;
+        verify(&body, cert, signature)
+    }
+
+    // 复制并验签，源对象签名无效时返回错误，用于跨信任边界传递对象
+    pub fn try_clone(&self) -> Result<Self, KVObjectError> {
+        let cloned = self.clone();
+        cloned.verfiy_kvhead()?;
+        Ok(cloned)
+    }
+
+    // 拆解为消息类型、证书、签名和结构体T
+    pub fn into_parts(self) -> (MsgType, Option<K::Cert>, Option<K::Sig>, T) {
+        (self.msg_type, self.cert, self.signature, self.t_obj)
     }
 
     pub fn get_body(&self) -> &T {
         &self.t_obj
     }
 
-    pub fn get_cert(&self) -> &Option<CertificateSm2> {
+    // 借出结构体T的可变引用，无法得知是否实际修改，保守地将签名标记为失效
+    pub fn body_mut(&mut self) -> &mut T {
+        self.raw_body = None;
+        self.stale = self.signature.is_some();
+        &mut self.t_obj
+    }
+
+    // 签名是否因body_mut而可能失效
+    pub fn is_signature_stale(&self) -> bool {
+        self.stale
+    }
+
+    pub fn get_raw_body(&self) -> Option<&[u8]> {
+        self.raw_body.as_deref()
+    }
+
+    pub fn get_cert(&self) -> &Option<K::Cert> {
         &self.cert
     }
 
-    pub fn get_signature(
-        &self,
-    ) -> &Option<<CertificateSm2 as Certificate>::Signature> {
+    // 输出便于调试阅读的对象内容
+    #[cfg(feature = "std")]
+    pub fn debug_dump(&self) -> String {
+        use core::fmt::Write;
+
+        let mut ret = String::new();
+        writeln!(ret, "msg_type: {:?}", self.msg_type).unwrap();
+        match &self.cert {
+            Some(cert) => writeln!(
+                ret,
+                "cert: {}",
+                cert.to_bytes().encode_hex_upper::<String>()
+            ),
+            None => writeln!(ret, "cert: none"),
+        }
+        .unwrap();
+        match &self.signature {
+            Some(signature) => writeln!(
+                ret,
+                "signature: {}",
+                signature.to_bytes().encode_hex_upper::<String>()
+            ),
+            None => writeln!(ret, "signature: none"),
+        }
+        .unwrap();
+        for key in self.t_obj.list_keys() {
+            match self.t_obj.get_key(key) {
+                Ok(value) => writeln!(ret, "{}: {}", key, value.encode_hex_upper::<String>()),
+                Err(err) => writeln!(ret, "{}: {:?}", key, err),
+            }
+            .unwrap();
+        }
+
+        ret
+    }
+
+    pub fn get_signature(&self) -> &Option<K::Sig> {
         &self.signature
     }
-}
 
-impl<T: KVBody> Bytes for KVObject<T> {
-    type BytesType = Vec<u8>;
+    // 验证签名，成功后返回对象携带的证书
+    pub fn verify_and_cert(&self) -> Result<K::Cert, KVObjectError> {
+        self.verfiy_kvhead()?;
+        self.cert.clone().ok_or(KVObjectError::KVHeadVerifyError)
+    }
 
-    type Error = KVObjectError;
+    // 验证签名，成功后返回签名原文的摘要，供审计记录
+    // 摘要与签名使用同一哈希算法H
+    pub fn verify_and_digest(&self) -> Result<[u8; 32], KVObjectError> {
+        self.verfiy_kvhead()?;
+        let mut hasher = H::default();
+        hasher.update(&self.signing_bytes()?[..]);
+        Ok(hasher.finalize())
+    }
 
-    fn from_bytes(bytes: &[u8]) -> Result<Self, KVObjectError> {
-        if bytes.len() < HEAD_TOTAL_LEN {
-            return Err(KVObjectError::DeSerializeError);
-        }
-        let msg_type = MsgType::from_bytes(&bytes[MSGTYPE_OFFSET..MSGTYPE_END])
-            .map_err(|_| KVObjectError::DeSerializeError)?;
-        let cert = CertificateSm2::from_bytes(&bytes[CERT_OFFSET..CERT_END])
-            .map_err(|_| KVObjectError::DeSerializeError)?;
-        let signature = <CertificateSm2 as Certificate>::Signature::from_bytes(
-            &bytes[SIGTURE_OFFSET..SIGTURE_END],
-        )
-        .map_err(|_| KVObjectError::DeSerializeError)?;
+    // 测试辅助：由种子构造ChaChaRng进行签名，相同种子得到相同签名，不可用于生产环境
+    #[cfg(feature = "test-support")]
+    pub fn fill_kvhead_seeded(&mut self, keypair: &K, seed: [u8; 32]) -> Result<(), KVObjectError> {
+        use rand::SeedableRng;
 
-        if bytes.len() == HEAD_TOTAL_LEN {
-            return Err(KVObjectError::DeSerializeError);
+        let mut rng = rand_chacha::ChaChaRng::from_seed(seed);
+        self.fill_kvhead(keypair, &mut rng)
+    }
+
+    // 更换签名者：以new_keypair对当前结构体T重新签名，替换证书和签名，签名失败时保留原签名
+    pub fn resign_with(
+        &mut self,
+        new_keypair: &K,
+        rng: &mut impl RngCore,
+    ) -> Result<(), KVObjectError> {
+        self.fill_kvhead(new_keypair, rng)
+    }
+
+    // 退回未签名状态，清除证书和签名，之后可用其他密钥对重新签名
+    // 失效标记stale仅表示“仍持有但可能失效的签名”（见body_mut），签名已清除，因此将其复位而不是置位，
+    // 否则未签名对象的is_signature_stale会返回true；验签仍因缺少证书返回UnsignedObject
+    pub fn downgrade_to_unsigned(&mut self) {
+        self.cert = None;
+        self.signature = None;
+        self.stale = false;
+    }
+
+    // 验签通过后将原始结构体数据重新解析为U，用于结构体版本升级
+    // 重新解析的正是签名覆盖的body_bytes，T声明了signed_keys时其余字节不受签名保护
+    pub fn verify_and_reinterpret<U: KVBody>(&self) -> Result<U, KVObjectError> {
+        self.verfiy_kvhead()?;
+        let body = C::decompress(&self.body_bytes())?;
+        U::from_bytes(&body).map_err(Into::into)
+    }
+
+    // 完整校验：证书和签名存在、签名有效、结构体T满足KVBody::validate
+    pub fn validate(&self) -> Result<(), KVObjectError> {
+        self.verfiy_kvhead()?;
+        self.t_obj.validate()
+    }
+
+    // 检查结构体T声明的消息类型与头部的消息类型一致
+    pub fn check_type_consistency(&self) -> Result<(), KVObjectError> {
+        match self.t_obj.declared_type() {
+            Some(declared) if declared != self.msg_type => Err(KVObjectError::TypeMismatch),
+            _ => Ok(()),
         }
+    }
 
-        // 序列化结构体T
-        let t_obj = T::from_bytes(&bytes[HEAD_TOTAL_LEN..])?;
+    // 验证签名，并确认证书属于受信任的签发者，返回匹配证书的下标
+    pub fn verify_against_any(&self, trusted: &[K::Cert]) -> Result<usize, KVObjectError> {
+        let cert = self.verify_and_cert()?;
+        trusted
+            .iter()
+            .position(|trusted_cert| *trusted_cert == cert)
+            .ok_or(KVObjectError::UntrustedIssuer)
+    }
 
-        Ok(Self {
-            msg_type,
-            cert: Some(cert),
-            signature: Some(signature),
-            t_obj,
+    // 验证签名，并检查时间戳落在 [now - skew, now + skew] 之内，没有时间戳的对象无法判断是否新鲜
+    pub fn verify_with_clock(&self, now: u64, skew: u64) -> Result<(), KVObjectError> {
+        self.verfiy_kvhead()?;
+        let timestamp = self.t_obj.timestamp().ok_or(KVObjectError::ClockSkew)?;
+        if timestamp < now.saturating_sub(skew) || timestamp > now.saturating_add(skew) {
+            return Err(KVObjectError::ClockSkew);
+        }
+        Ok(())
+    }
+
+    // 计算证书的摘要，用于按签发者快速分组，未签名时返回None
+    pub fn cert_fingerprint<D: Default + Hasher<Output = [u8; 32]>>(&self) -> Option<[u8; 32]> {
+        self.cert.as_ref().map(|cert| {
+            let mut hasher = D::default();
+            hasher.update(cert.to_bytes().as_ref());
+            hasher.finalize()
         })
     }
 
-    fn to_bytes(&self) -> Self::BytesType {
+    pub fn cert_fingerprint_sm3(&self) -> Option<[u8; 32]> {
+        self.cert_fingerprint::<Sm3>()
+    }
+
+    // 遍历所有key及其当前值，读取失败的key返回错误而不是被跳过
+    pub fn attrs(
+        &self,
+    ) -> impl Iterator<Item = Result<(&'static str, Vec<u8>), KVObjectError>> + '_ {
+        self.t_obj
+            .list_keys()
+            .iter()
+            .map(move |key| self.t_obj.get_key(key).map(|value| (*key, value)))
+    }
+
+    // 按路径读取嵌套字段
+    pub fn get_path(&self, path: &str) -> Result<Vec<u8>, KVObjectError> {
+        self.t_obj.get_path(path)
+    }
+
+    // 按路径写入嵌套字段，路径首段为签名覆盖的key时将签名标记为失效
+    pub fn set_path(&mut self, path: &str, value: &[u8]) -> Result<(), KVObjectError> {
+        self.t_obj.set_path(path, value)?;
+        self.raw_body = None;
+        let top = path.split('.').next().unwrap_or(path);
+        if Self::is_signed_key(top) {
+            self.stale = self.signature.is_some();
+        }
+        Ok(())
+    }
+
+    // 对象的内容标识，为去掉元数据后的序列化数据（含证书和签名）的Sm3摘要
+    // 元数据不参与签名，附加或修改元数据不改变内容标识
+    pub fn content_id(&self) -> [u8; 32] {
+        let body = self.body_bytes();
+        let mut bytes = Vec::<u8>::with_capacity(Self::HEAD_TOTAL_LEN + body.len());
+        self.push_head(&mut bytes, self.head_flags() & !FLAG_METADATA);
+        bytes.extend_from_slice(&body);
+
+        let mut hasher = Sm3::default();
+        hasher.update(&bytes[..]);
+        hasher.finalize()
+    }
+
+    // 去掉证书和签名后的副本，to_bytes写出默认头部，用于计算与签名者无关的标识
+    pub fn without_signature(&self) -> Self {
+        self.clone_unsigned()
+    }
+
+    // 省略证书的序列化格式，用于双方已共享签发者证书的信道
+    pub fn to_bytes_no_cert(&self) -> Vec<u8> {
         let mut ret = Vec::<u8>::new();
 
         ret.extend_from_slice(self.msg_type.to_bytes().as_ref());
-        if let Some(cert) = &self.cert {
-            ret.extend_from_slice(cert.to_bytes().as_ref());
+        if self.signature.is_some() {
+            ret.push(FLAG_NO_CERT | FLAG_SIGNED | self.body_flags());
         } else {
-            ret.extend_from_slice(CertificateSm2::default().to_bytes().as_ref());
+            ret.push(FLAG_NO_CERT | self.body_flags());
         }
         if let Some(signature) = &self.signature {
             ret.extend_from_slice(signature.to_bytes().as_ref());
         } else {
-            ret.extend_from_slice(<CertificateSm2 as Certificate>::Signature::default().to_bytes().as_ref());
+            ret.extend_from_slice(K::Sig::default().to_bytes().as_ref());
         }
-        ret.extend_from_slice(self.t_obj.to_bytes().as_ref());
+        ret.extend_from_slice(&self.body_section());
 
         ret
     }
-}
 
-impl<T: KVBody> KValueObject for KVObject<T> {
-    type KeyPair = KeyPairSm2;
+    // 解析to_bytes_no_cert的输出，证书由调用者提供，验签时使用该证书
+    pub fn from_bytes_with_cert(bytes: &[u8], cert: K::Cert) -> Result<Self, KVObjectError> {
+        let head_len = FLAGS_END + K::SIGTURE_LEN;
+        if bytes.len() < head_len {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let msg_type = MsgType::from_bytes(&bytes[MSGTYPE_OFFSET..MSGTYPE_END])
+            .map_err(|_| KVObjectError::DeSerializeError)?;
+        let flags = bytes[FLAGS_OFFSET];
+        check_flags(flags)?;
+        if flags & FLAG_NO_CERT == 0 {
+            return Err(KVObjectError::DeSerializeError);
+        }
 
-    type Certificate = CertificateSm2;
+        let signature = if flags & FLAG_SIGNED != 0 {
+            Some(
+                K::Sig::from_bytes(&bytes[FLAGS_END..head_len])
+                    .map_err(|_| KVObjectError::DeSerializeError)?,
+            )
+        } else {
+            None
+        };
 
-    type Signature = <CertificateSm2 as Certificate>::Signature;
+        let (body, metadata) = split_body_section(flags, &bytes[head_len..])?;
+        let t_obj = Self::decode_body(flags, body)?;
 
-    fn fill_kvhead(
-        &mut self,
-        keypair: &Self::KeyPair,
-        rng: &mut impl RngCore,
-    ) -> Result<(), KVObjectError> {
-        let body_ = self.t_obj.to_bytes();
+        Ok(Self {
+            msg_type,
+            cert: Some(cert),
+            signature,
+            t_obj,
+            raw_body: Some(Vec::<u8>::from(body)),
+            metadata,
+            stale: false,
+            marker: PhantomData,
+        })
+    }
 
-        let signature = keypair
-            .sign::<Sm3, _>(body_.as_ref(), rng)
-            .map_err(|_| KVObjectError::SerializeSignError)?;
+    // 在序列化数据后附加CRC32（小端序），用于存储介质上的损坏检测，不属于签名范围
+    pub fn to_bytes_with_crc(&self) -> Vec<u8> {
+        let mut ret = self.to_bytes();
+        let crc = crc32(&ret);
+        ret.extend_from_slice(&crc.to_le_bytes());
+        ret
+    }
 
-        self.signature = Some(signature);
-        self.cert = Some(keypair.get_certificate());
+    // 先校验CRC32再解析，数据损坏时返回ChecksumMismatch
+    pub fn from_bytes_with_crc(bytes: &[u8]) -> Result<Self, KVObjectError> {
+        if bytes.len() < CRC_LEN {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let (data, crc_bytes) = bytes.split_at(bytes.len() - CRC_LEN);
+        let mut crc = [0u8; CRC_LEN];
+        crc.clone_from_slice(crc_bytes);
+        if u32::from_le_bytes(crc) != crc32(data) {
+            return Err(KVObjectError::ChecksumMismatch);
+        }
+        Self::from_bytes(data)
+    }
 
-        Ok(())
+    // 按结构体T声明的长度严格校验总长度，截断或多余填充时返回LengthMismatch
+    // 压缩或附带元数据的数据长度与byte_len不符，同样被拒绝
+    pub fn from_bytes_fixed(bytes: &[u8]) -> Result<Self, KVObjectError> {
+        if let Some(size) = T::size_hint() {
+            if bytes.len() != Self::HEAD_TOTAL_LEN + size {
+                return Err(KVObjectError::LengthMismatch);
+            }
+        }
+        let obj = Self::from_bytes(bytes)?;
+        if bytes.len() != Self::HEAD_TOTAL_LEN + obj.t_obj.byte_len() {
+            return Err(KVObjectError::LengthMismatch);
+        }
+        Ok(obj)
     }
 
-    fn verfiy_kvhead(&self) -> Result<(), KVObjectError> {
-        // 根据证书链验证证书，略过
-        // 根据证书验证签名
-        if self.cert.is_none() || self.signature.is_none() {
-            return Err(KVObjectError::KVHeadVerifyError);
+    // 在序列化数据前加上魔数，便于工具识别文件类型
+    pub fn to_bytes_with_magic(&self) -> Vec<u8> {
+        let bytes = self.to_bytes();
+        let mut ret = Vec::with_capacity(MAGIC.len() + bytes.len());
+        ret.extend_from_slice(&MAGIC);
+        ret.extend_from_slice(&bytes);
+        ret
+    }
+
+    // 先检查魔数再解析，魔数不符时返回BadMagic
+    pub fn from_bytes_with_magic(bytes: &[u8]) -> Result<Self, KVObjectError> {
+        if bytes.len() < MAGIC.len() || bytes[..MAGIC.len()] != MAGIC {
+            return Err(KVObjectError::BadMagic);
         }
-        if let Some(cert) = &self.cert {
-            if let Some(signature) = &self.signature {
-                let isvalid = cert.verify::<Sm3>(self.t_obj.to_bytes().as_ref(), &signature);
-                if !isvalid {
-                    return Err(KVObjectError::KVHeadVerifyError);
-                }
-            }
+        Self::from_bytes(&bytes[MAGIC.len()..])
+    }
+
+    // 加上长度前缀，便于在字节流中连续传输多个对象
+    // 超过MAX_FRAME_LEN的对象解码器无法接收，返回SerializeError
+    pub fn to_frame(&self) -> Result<Vec<u8>, KVObjectError> {
+        let bytes = self.to_bytes();
+        if bytes.len() > MAX_FRAME_LEN {
+            return Err(KVObjectError::SerializeError);
         }
-        Ok(())
+        let len = u32::try_from(bytes.len()).map_err(|_| KVObjectError::SerializeError)?;
+        let mut ret = Vec::with_capacity(FRAME_LEN_LEN + bytes.len());
+        ret.extend_from_slice(&len.to_le_bytes());
+        ret.extend_from_slice(&bytes);
+        Ok(ret)
     }
-}
 
-impl<T: KVBody> AttrProxy for KVObject<T> {
-    type Byte = Vec<u8>;
+    // 序列化为大写十六进制字符串
+    pub fn to_hex(&self) -> String {
+        self.to_bytes().encode_hex_upper::<String>()
+    }
 
-    // 根据key读取值
-    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
-        self.t_obj.get_key(key)
+    pub fn from_hex(s: &str) -> Result<Self, KVObjectError> {
+        let bytes = Vec::<u8>::from_hex(s).map_err(|_| KVObjectError::DeSerializeError)?;
+        Self::from_bytes(&bytes)
     }
 
-    // 根据key写取值
-    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
-        self.t_obj.set_key(key, value)
+    // 导出为JSON信封，cert和signature为大写十六进制（未签名时为null），body为base64编码的结构体数据
+    // 元数据不参与签名，不写入信封
+    pub fn to_json_envelope(&self) -> String {
+        let cert = self
+            .cert
+            .as_ref()
+            .map(|cert| cert.to_bytes().as_ref().encode_hex_upper::<String>());
+        let signature = self
+            .signature
+            .as_ref()
+            .map(|sig| sig.to_bytes().as_ref().encode_hex_upper::<String>());
+        let body = envelope::base64_encode(&self.body_bytes());
+        envelope::write_object(&[
+            ("type", Some(self.msg_type.name())),
+            ("cert", cert.as_deref()),
+            ("signature", signature.as_deref()),
+            ("body", Some(&body)),
+        ])
+    }
+
+    pub fn from_json_envelope(s: &str) -> Result<Self, KVObjectError> {
+        let fields = envelope::read_object(s)?;
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+                .ok_or(KVObjectError::DeSerializeError)
+        };
+
+        let type_name = field("type")?.ok_or(KVObjectError::FindTypeError)?;
+        let msg_type = MsgType::from_name(type_name).ok_or(KVObjectError::FindTypeError)?;
+        let cert = match field("cert")? {
+            Some(hex) => Some(K::Cert::from_bytes(
+                &Vec::<u8>::from_hex(hex).map_err(|_| KVObjectError::DeSerializeError)?,
+            )?),
+            None => None,
+        };
+        let signature = match field("signature")? {
+            Some(hex) => Some(K::Sig::from_bytes(
+                &Vec::<u8>::from_hex(hex).map_err(|_| KVObjectError::DeSerializeError)?,
+            )?),
+            None => None,
+        };
+        let body = envelope::base64_decode(field("body")?.ok_or(KVObjectError::DeSerializeError)?)?;
+        let t_obj = Self::decode_body(Self::codec_flags(), &body)?;
+
+        Ok(Self {
+            msg_type,
+            cert,
+            signature,
+            t_obj,
+            raw_body: Some(body),
+            metadata: Vec::new(),
+            stale: false,
+            marker: PhantomData,
+        })
+    }
+
+    // 通过serde以bincode编码整个对象，证书和签名为原始字节，与to_bytes的格式无关
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, KVObjectError>
+    where
+        Self: Serialize,
+    {
+        bincode::serialize(self).map_err(|_| KVObjectError::SerializeError)
+    }
+
+    // 解析to_bincode的输出
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, KVObjectError>
+    where
+        Self: for<'de> Deserialize<'de>,
+    {
+        bincode::deserialize(bytes).map_err(|_| KVObjectError::DeSerializeError)
+    }
+
+    // 解析失败时将错误包装为Labeled，label用于标识出错的对象
+    pub fn from_bytes_labeled(bytes: &[u8], label: &'static str) -> Result<Self, KVObjectError> {
+        Self::from_bytes(bytes).map_err(|e| KVObjectError::Labeled {
+            label,
+            source: Box::new(e),
+        })
+    }
+
+    // 解析并用对象自带的证书验签，返回结构体T和签发者证书，不检查签发者是否可信
+    pub fn open_with_issuer(bytes: &[u8]) -> Result<(T, K::Cert), KVObjectError> {
+        let obj = Self::from_bytes(bytes)?;
+        let cert = obj.verify_and_cert()?;
+        Ok((obj.t_obj, cert))
+    }
+
+    // 解析并用trusted验签，再由pred检查结构体T的业务规则，不满足时返回BodyRejected
+    pub fn open_validated<F: Fn(&T) -> bool>(
+        bytes: &[u8],
+        trusted: &K::Cert,
+        pred: F,
+    ) -> Result<T, KVObjectError> {
+        let obj = Self::from_bytes(bytes)?;
+        obj.verify_against_any(core::slice::from_ref(trusted))?;
+        if !pred(&obj.t_obj) {
+            return Err(KVObjectError::BodyRejected);
+        }
+        Ok(obj.t_obj)
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H, C: BodyCodec> KVObjectGeneric<T, K, H, C> {
+    const CERT_OFFSET: usize = FLAGS_END;
+    const SIGTURE_OFFSET: usize = Self::CERT_OFFSET + K::CERT_LEN;
+    const HEAD_TOTAL_LEN: usize = Self::SIGTURE_OFFSET + K::SIGTURE_LEN;
+
+    // 序列化使用的结构体数据（经C编码），与to_bytes中的数据段一致
+    // 优先使用保留的原始数据，否则为canonical_bytes，保证签名原文与写出的字节一致
+    pub fn body_bytes(&self) -> Vec<u8> {
+        match &self.raw_body {
+            Some(raw_body) => raw_body.clone(),
+            None => C::compress(&self.t_obj.canonical_bytes()),
+        }
+    }
+
+    // 编码方式对应的标志位
+    fn codec_flags() -> u8 {
+        if C::IDENTITY {
+            0
+        } else {
+            FLAG_COMPRESSED
+        }
+    }
+
+    // 签名原文，前面附加数据的长度（u32小端序），截断或追加数据后签名不再有效
+    // 未声明signed_keys时为序列化时写出的结构体T数据（经C编码），即body_bytes：
+    // 解析得到的对象为收到的原始数据，新建的对象为canonical_bytes经C编码的结果，
+    // 因此签名覆盖的正是to_bytes写出的字节，不能被宽松的T::from_bytes忽略的字节绕过
+    // 声明了signed_keys时以signed_keys为准：依次写入各key值的长度（u32小端序）和值，
+    // 不经C编码也不使用canonical_bytes，其余数据（包括保留的原始数据）不受签名保护
+    fn signing_bytes(&self) -> Result<Vec<u8>, KVObjectError> {
+        Self::signing_bytes_of(&self.t_obj, &self.body_bytes())
+    }
+
+    // body为序列化时的结构体T数据，仅在未声明signed_keys时使用
+    fn signing_bytes_of(t_obj: &T, body: &[u8]) -> Result<Vec<u8>, KVObjectError> {
+        let signed_keys = T::signed_keys();
+        let mut ret;
+        if signed_keys.is_empty() {
+            ret = Vec::<u8>::with_capacity(LEN_PREFIX_LEN + body.len());
+            push_prefixed(&mut ret, body);
+        } else {
+            let mut keys = Vec::<u8>::new();
+            for key in signed_keys {
+                push_prefixed(&mut keys, &t_obj.get_key(key)?);
+            }
+            ret = Vec::<u8>::with_capacity(LEN_PREFIX_LEN + keys.len());
+            push_prefixed(&mut ret, &keys);
+        }
+        Ok(ret)
+    }
+
+    // 按压缩标志解码结构体T的数据，标志须与编码方式C一致
+    fn decode_body(flags: u8, body: &[u8]) -> Result<T, KVObjectError> {
+        if (flags & FLAG_COMPRESSED == 0) != C::IDENTITY {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        if C::IDENTITY {
+            Self::parse_body(body)
+        } else {
+            Self::parse_body(&C::decompress(body)?)
+        }
+    }
+
+    fn parse_body(body: &[u8]) -> Result<T, KVObjectError> {
+        if body.is_empty() {
+            return Err(KVObjectError::DeSerializeError);
+        }
+
+        if let Some(size) = T::size_hint() {
+            if body.len() != size {
+                return Err(KVObjectError::BodyLengthMismatch);
+            }
+        }
+
+        // 序列化结构体T
+        T::from_bytes(body).map_err(Into::into)
+    }
+
+    // 描述结构体T数据段的标志位
+    fn body_flags(&self) -> u8 {
+        let flags = FORMAT_VERSION << VERSION_SHIFT | Self::codec_flags();
+        if self.metadata.is_empty() {
+            flags
+        } else {
+            flags | FLAG_METADATA
+        }
+    }
+
+    // to_bytes写入的标志位
+    fn head_flags(&self) -> u8 {
+        if self.cert.is_some() && self.signature.is_some() {
+            FLAG_SIGNED | self.body_flags()
+        } else {
+            self.body_flags()
+        }
+    }
+
+    // 写入头部：消息类型、标志位、证书和签名，未签名时证书和签名写为默认值
+    fn push_head(&self, ret: &mut Vec<u8>, flags: u8) {
+        ret.extend_from_slice(self.msg_type.to_bytes().as_ref());
+        ret.push(flags);
+        if let Some(cert) = &self.cert {
+            ret.extend_from_slice(cert.to_bytes().as_ref());
+        } else {
+            ret.extend_from_slice(K::Cert::default().to_bytes().as_ref());
+        }
+        if let Some(signature) = &self.signature {
+            ret.extend_from_slice(signature.to_bytes().as_ref());
+        } else {
+            ret.extend_from_slice(K::Sig::default().to_bytes().as_ref());
+        }
+    }
+
+    // 头部之后的数据段。有元数据时结构体T数据前加u32小端序长度，其后为元数据
+    fn body_section(&self) -> Vec<u8> {
+        let body = self.body_bytes();
+        if self.metadata.is_empty() {
+            return body;
+        }
+        let mut ret = Vec::<u8>::with_capacity(LEN_PREFIX_LEN + body.len());
+        push_prefixed(&mut ret, &body);
+        for (key, value) in &self.metadata {
+            push_prefixed(&mut ret, key.as_bytes());
+            push_prefixed(&mut ret, value);
+        }
+        ret
+    }
+
+    // 读取元数据
+    pub fn get_metadata(&self, key: &str) -> Option<&[u8]> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_slice())
+    }
+
+    // 写入元数据，已有同名key时覆盖。元数据不参与签名，写入后签名仍然有效
+    pub fn set_metadata(&mut self, key: &str, value: &[u8]) {
+        match self.metadata.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = Vec::<u8>::from(value),
+            None => self
+                .metadata
+                .push((String::from(key), Vec::<u8>::from(value))),
+        }
+    }
+
+    // 按to_bytes的格式直接写入输出流，不拼接完整的序列化数据
+    #[cfg(feature = "std")]
+    pub fn write_to_io<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        w.write_all(self.msg_type.to_bytes().as_ref())?;
+        w.write_all(&[self.head_flags()])?;
+        match &self.cert {
+            Some(cert) => w.write_all(cert.to_bytes().as_ref())?,
+            None => w.write_all(K::Cert::default().to_bytes().as_ref())?,
+        }
+        match &self.signature {
+            Some(signature) => w.write_all(signature.to_bytes().as_ref())?,
+            None => w.write_all(K::Sig::default().to_bytes().as_ref())?,
+        }
+        match &self.raw_body {
+            Some(raw_body) if self.metadata.is_empty() => w.write_all(raw_body),
+            _ => w.write_all(&self.body_section()),
+        }
+    }
+
+    // 逐段比较to_bytes的结果，遇到第一处不同即返回，两侧都保留原始数据时不序列化结构体T
+    pub fn bytes_eq(&self, other: &Self) -> bool {
+        if self.msg_type != other.msg_type || self.head_flags() != other.head_flags() {
+            return false;
+        }
+        let default_cert = K::Cert::default();
+        let cert_bytes = |cert: &Option<K::Cert>| cert.as_ref().unwrap_or(&default_cert).to_bytes();
+        if cert_bytes(&self.cert).as_ref() != cert_bytes(&other.cert).as_ref() {
+            return false;
+        }
+        let default_sig = K::Sig::default();
+        let sig_bytes =
+            |signature: &Option<K::Sig>| signature.as_ref().unwrap_or(&default_sig).to_bytes();
+        if sig_bytes(&self.signature).as_ref() != sig_bytes(&other.signature).as_ref() {
+            return false;
+        }
+        if self.metadata != other.metadata {
+            return false;
+        }
+        match (&self.raw_body, &other.raw_body) {
+            (Some(raw_body), Some(other_raw_body)) => raw_body == other_raw_body,
+            _ => self.body_bytes() == other.body_bytes(),
+        }
+    }
+
+    // to_bytes输出的长度
+    pub fn serialized_len(&self) -> usize {
+        let body_len = match &self.raw_body {
+            Some(raw_body) => raw_body.len(),
+            None if C::IDENTITY => self.t_obj.byte_len(),
+            None => self.body_bytes().len(),
+        };
+        if self.metadata.is_empty() {
+            return Self::HEAD_TOTAL_LEN + body_len;
+        }
+        let metadata_len: usize = self
+            .metadata
+            .iter()
+            .map(|(key, value)| 2 * LEN_PREFIX_LEN + key.len() + value.len())
+            .sum();
+        Self::HEAD_TOTAL_LEN + LEN_PREFIX_LEN + body_len + metadata_len
+    }
+
+    fn is_signed_key(key: &str) -> bool {
+        let signed_keys = T::signed_keys();
+        signed_keys.is_empty() || signed_keys.contains(&key)
+    }
+
+    // 与checked_get_key相同：先映射旧名，再拒绝空key和仅含空白的key
+    fn checked_key(key: &str) -> Result<&str, KVObjectError> {
+        let key = T::key_alias(key);
+        if key.trim().is_empty() {
+            return Err(KVObjectError::KeyIndexError);
+        }
+        Ok(key)
+    }
+}
+
+impl<T: KVBody, H, C> KVObjectGeneric<T, KeyPairSm2, H, C> {
+    // 签发者证书的压缩公钥，未签名时返回None
+    pub fn issuer(&self) -> Option<[u8; CERT_LEN]> {
+        self.cert.as_ref().map(|cert| {
+            let mut ret = [0u8; CERT_LEN];
+            ret.clone_from_slice(&cert.to_bytes());
+            ret
+        })
+    }
+}
+
+impl<T: KVBody, H: Default + Hasher<Output = [u8; 32]>, C: BodyCodec>
+    KVObjectGeneric<T, KeyPairSm2, H, C>
+{
+    // 以密钥标识代替证书的序列化格式：标识 || to_bytes_no_cert，未签名对象没有标识
+    pub fn to_bytes_keyid(&self) -> Result<Vec<u8>, KVObjectError> {
+        let cert = self.cert.as_ref().ok_or(KVObjectError::UnsignedObject)?;
+        let mut ret = Vec::<u8>::with_capacity(KEY_ID_LEN + self.serialized_len());
+        ret.extend_from_slice(&key_id(cert));
+        ret.extend_from_slice(&self.to_bytes_no_cert());
+        Ok(ret)
+    }
+
+    // 解析to_bytes_keyid的输出，标识未登记时返回UntrustedIssuer
+    pub fn from_bytes_keyid(bytes: &[u8], registry: &KeyRegistry) -> Result<Self, KVObjectError> {
+        if bytes.len() < KEY_ID_LEN {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let mut id = [0u8; KEY_ID_LEN];
+        id.clone_from_slice(&bytes[..KEY_ID_LEN]);
+        let cert = registry
+            .get(&id)
+            .ok_or(KVObjectError::UntrustedIssuer)?
+            .clone();
+        Self::from_bytes_with_cert(&bytes[KEY_ID_LEN..], cert)
+    }
+
+    // 按SM2标准以用户ID计算ZA，对 ZA || 签名原文 签名并填充头部
+    // 注意fill_kvhead和verfiy_kvhead不附加ZA，两种方式签出的对象不能互相验证
+    pub fn sign_with_id(
+        &mut self,
+        keypair: &KeyPairSm2,
+        id: &[u8],
+        rng: &mut impl RngCore,
+    ) -> Result<(), KVObjectError> {
+        use asymmetric_crypto::prelude::Keypair;
+
+        let cert = keypair.get_certificate();
+        let mut preimage = Vec::from(&cert.za(id)?[..]);
+        preimage.extend_from_slice(&self.signing_bytes()?);
+
+        let signature = keypair
+            .sign::<H, _>(&preimage, rng)
+            .map_err(|_| KVObjectError::SerializeSignError)?;
+
+        self.signature = Some(signature);
+        self.cert = Some(cert);
+        self.stale = false;
+
+        Ok(())
+    }
+
+    // 以给定用户ID验证sign_with_id生成的签名，失败原因的映射与verify_detailed一致
+    // 证书不在曲线上、无法计算ZA时视为签名无效
+    pub fn verify_with_id(&self, id: &[u8]) -> Result<(), KVObjectError> {
+        self.verify_detailed_by(|body, cert, signature| {
+            check_signature::<KeyPairSm2>(cert, signature, || match cert.za(id) {
+                Ok(za) => {
+                    let mut preimage = Vec::from(&za[..]);
+                    preimage.extend_from_slice(body);
+                    cert.verify::<H>(&preimage, signature)
+                }
+                Err(_) => false,
+            })
+        })
+        .map_err(KVObjectError::from)
+    }
+
+    // 与verfiy_kvhead相同，多次验签时通过ctx共享预计算结果
+    pub fn verify_with_ctx(&self, ctx: &Sm2Context) -> Result<(), KVObjectError> {
+        self.verify_detailed_by(|body, cert, signature| {
+            check_signature::<KeyPairSm2>(cert, signature, || {
+                ctx.verify::<H>(cert, body, signature)
+            })
+        })
+        .map_err(KVObjectError::from)
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H, C: BodyCodec> Bytes for KVObjectGeneric<T, K, H, C> {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, KVObjectError> {
+        let mut timer = ParseTimer::start();
+        if bytes.len() < Self::HEAD_TOTAL_LEN {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let msg_type = MsgType::from_bytes(&bytes[MSGTYPE_OFFSET..MSGTYPE_END])
+            .map_err(|_| KVObjectError::DeSerializeError)?;
+        let flags = bytes[FLAGS_OFFSET];
+        check_flags(flags)?;
+        if flags & FLAG_NO_CERT != 0 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        timer.header_done();
+
+        // 未签名对象的证书和签名区域为默认值，不做解析
+        let (cert, signature) = if flags & FLAG_SIGNED != 0 {
+            // 默认证书只是占位值，不视为有效证书，也未必是合法的压缩编码
+            let cert_bytes = &bytes[Self::CERT_OFFSET..Self::SIGTURE_OFFSET];
+            let cert = if cert_bytes == K::Cert::default().to_bytes().as_ref() {
+                None
+            } else {
+                Some(K::Cert::from_bytes(cert_bytes).map_err(|_| KVObjectError::DeSerializeError)?)
+            };
+            // 默认签名同为占位值，且未必能通过签名自身的格式检查
+            let sig_bytes = &bytes[Self::SIGTURE_OFFSET..Self::HEAD_TOTAL_LEN];
+            let signature = if sig_bytes == K::Sig::default().to_bytes().as_ref() {
+                None
+            } else {
+                Some(K::Sig::from_bytes(sig_bytes).map_err(|_| KVObjectError::DeSerializeError)?)
+            };
+            (cert, signature)
+        } else {
+            (None, None)
+        };
+        timer.cert_done();
+
+        let (body, metadata) = split_body_section(flags, &bytes[Self::HEAD_TOTAL_LEN..])?;
+        let t_obj = Self::decode_body(flags, body)?;
+        timer.body_done();
+
+        Ok(Self {
+            msg_type,
+            cert,
+            signature,
+            t_obj,
+            raw_body: Some(Vec::<u8>::from(body)),
+            metadata,
+            stale: false,
+            marker: PhantomData,
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        let mut ret = Vec::<u8>::with_capacity(self.serialized_len());
+
+        self.push_head(&mut ret, self.head_flags());
+        ret.extend_from_slice(&self.body_section());
+
+        ret
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H, C: BodyCodec> TryFrom<&[u8]> for KVObjectGeneric<T, K, H, C> {
+    type Error = KVObjectError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+// 以T::DEFAULT_MSG_TYPE构造未签名对象
+impl<T: KVBody, K: KVKeypair, H: Default + Hasher<Output = [u8; 32]>, C: BodyCodec> From<T>
+    for KVObjectGeneric<T, K, H, C>
+{
+    fn from(t_obj: T) -> Self {
+        Self::new(T::DEFAULT_MSG_TYPE, t_obj)
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H: Default + Hasher<Output = [u8; 32]>, C: BodyCodec> KValueObject
+    for KVObjectGeneric<T, K, H, C>
+{
+    type KeyPair = K;
+
+    type Certificate = K::Cert;
+
+    type Signature = K::Sig;
+
+    type Hasher = H;
+
+    fn fill_kvhead(
+        &mut self,
+        keypair: &Self::KeyPair,
+        rng: &mut impl RngCore,
+    ) -> Result<(), KVObjectError> {
+        let body_ = self.signing_bytes()?;
+
+        let signature = keypair
+            .sign::<Self::Hasher, _>(body_.as_ref(), rng)
+            .map_err(|_| KVObjectError::SerializeSignError)?;
+
+        self.signature = Some(signature);
+        self.cert = Some(keypair.get_certificate());
+        self.stale = false;
+
+        Ok(())
+    }
+
+    // 直接对签名原文验签，不附加用户ID的ZA，自定义ID的签名见verify_with_id
+    fn verfiy_kvhead(&self) -> Result<(), KVObjectError> {
+        self.verify_detailed().map_err(KVObjectError::from)
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H, C: BodyCodec> AttrProxy for KVObjectGeneric<T, K, H, C> {
+    type Byte = Vec<u8>;
+
+    // 根据key读取值
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        self.t_obj.checked_get_key(T::key_alias(key))
+    }
+
+    // 根据key写取值
+    // 修改签名覆盖的key时与body_mut相同，保留证书和签名并将签名标记为失效
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        let key = T::key_alias(key);
+        if let Ok(width) = self.t_obj.key_width(key) {
+            if value.len() != width {
+                return Err(KVObjectError::ValueLength {
+                    expected: width,
+                    got: value.len(),
+                });
+            }
+        }
+        self.t_obj.checked_set_key(key, value)?;
+        self.raw_body = None;
+        if Self::is_signed_key(key) {
+            self.stale = self.signature.is_some();
+        }
+        Ok(())
+    }
+
+    fn get_key_into(&self, key: &str, out: &mut Self::Byte) -> Result<(), KVObjectError> {
+        self.t_obj.get_key_into(Self::checked_key(key)?, out)
+    }
+
+    fn list_keys(&self) -> &'static [&'static str] {
+        self.t_obj.list_keys()
+    }
+
+    fn key_len(&self, key: &str) -> Result<usize, KVObjectError> {
+        self.t_obj.key_len(Self::checked_key(key)?)
+    }
+
+    fn key_byte_len(&self, key: &str) -> Result<usize, KVObjectError> {
+        self.t_obj.key_byte_len(Self::checked_key(key)?)
+    }
+
+    fn reserve_attrs(&mut self, additional: usize) {
+        self.t_obj.reserve_attrs(additional)
+    }
+
+    fn reset(&mut self) -> Result<(), KVObjectError> {
+        self.t_obj.reset()?;
+        self.raw_body = None;
+        self.stale = self.signature.is_some();
+        Ok(())
+    }
+}
+
+// 批量验证，返回验证失败对象的下标
+#[cfg(not(feature = "rayon"))]
+pub fn verify_batch<
+    T: KVBody,
+    K: KVKeypair,
+    H: Default + Hasher<Output = [u8; 32]>,
+    C: BodyCodec,
+>(
+    objs: &[KVObjectGeneric<T, K, H, C>],
+) -> Result<(), Vec<usize>> {
+    let failed: Vec<usize> = objs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, obj)| obj.verfiy_kvhead().err().map(|_| i))
+        .collect();
+
+    batch_result(failed)
+}
+
+// 批量验证，返回验证失败对象的下标
+#[cfg(feature = "rayon")]
+pub fn verify_batch<T: KVBody, K: KVKeypair, H: Default + Hasher<Output = [u8; 32]>, C: BodyCodec>(
+    objs: &[KVObjectGeneric<T, K, H, C>],
+) -> Result<(), Vec<usize>>
+where
+    KVObjectGeneric<T, K, H, C>: Sync,
+{
+    use rayon::prelude::*;
+
+    let failed: Vec<usize> = objs
+        .par_iter()
+        .enumerate()
+        .filter_map(|(i, obj)| obj.verfiy_kvhead().err().map(|_| i))
+        .collect();
+
+    batch_result(failed)
+}
+
+// 批量序列化，结果与objs顺序一致
+#[cfg(not(feature = "rayon"))]
+pub fn serialize_batch<T: KVBody, K: KVKeypair, H, C: BodyCodec>(
+    objs: &[KVObjectGeneric<T, K, H, C>],
+) -> Vec<Vec<u8>> {
+    objs.iter().map(|obj| obj.to_bytes()).collect()
+}
+
+// 批量序列化，结果与objs顺序一致
+#[cfg(feature = "rayon")]
+pub fn serialize_batch<T: KVBody, K: KVKeypair, H, C: BodyCodec>(
+    objs: &[KVObjectGeneric<T, K, H, C>],
+) -> Vec<Vec<u8>>
+where
+    KVObjectGeneric<T, K, H, C>: Sync,
+{
+    use rayon::prelude::*;
+
+    objs.par_iter().map(|obj| obj.to_bytes()).collect()
+}
+
+// 以各对象的content_id为叶子计算Merkle根，父节点为H(左 || 右)
+// 某层节点数为奇数时复制最后一个节点，空集合返回全零
+// 因此[a, b, c]与[a, b, c, c]的根相同，需要区分时调用方应另行承诺对象个数
+pub fn merkle_root<T: KVBody, H: Default + Hasher<Output = [u8; 32]>>(
+    objs: &[KVObject<T>],
+) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = objs.iter().map(|obj| obj.content_id()).collect();
+    if level.is_empty() {
+        return [0u8; 32];
+    }
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(level[level.len() - 1]);
+        }
+        level = level
+            .chunks_exact(2)
+            .map(|pair| {
+                let mut hasher = H::default();
+                hasher.update(&pair[0][..]);
+                hasher.update(&pair[1][..]);
+                hasher.finalize()
+            })
+            .collect();
+    }
+    level[0]
+}
+
+// 更换签名密钥时以new_keypair依次重新签名，遇到第一个失败即返回AtIndex，此前的对象已重新签名
+pub fn resign_all<T: KVBody>(
+    objs: &mut [KVObject<T>],
+    new_keypair: &KeyPairSm2,
+    rng: &mut impl RngCore,
+) -> Result<(), KVObjectError> {
+    for (index, obj) in objs.iter_mut().enumerate() {
+        obj.resign_with(new_keypair, rng)
+            .map_err(|e| KVObjectError::AtIndex {
+                index,
+                source: Box::new(e),
+            })?;
+    }
+    Ok(())
+}
+
+// 验签失败的具体原因
+#[derive(Debug, PartialEq)]
+pub enum VerifyFailure {
+    MissingCert,
+    MissingSignature,
+    DefaultCert,
+    DefaultSignature,
+    BadSignature,
+    // 签名后借出过body_mut，签名可能已失效
+    StaleSignature,
+    // 无法由结构体T生成签名原文
+    InvalidBody(KVObjectError),
+}
+
+// 缺少证书或签名、使用占位值均视为未签名对象
+impl From<VerifyFailure> for KVObjectError {
+    fn from(failure: VerifyFailure) -> Self {
+        match failure {
+            VerifyFailure::MissingCert
+            | VerifyFailure::MissingSignature
+            | VerifyFailure::DefaultCert
+            | VerifyFailure::DefaultSignature => KVObjectError::UnsignedObject,
+            VerifyFailure::BadSignature | VerifyFailure::StaleSignature => {
+                KVObjectError::KVHeadVerifyError
+            }
+            VerifyFailure::InvalidBody(err) => err,
+        }
+    }
+}
+
+// 数据段中各长度前缀（u32小端序）的长度
+const LEN_PREFIX_LEN: usize = 4;
+
+fn push_prefixed(ret: &mut Vec<u8>, data: &[u8]) {
+    ret.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    ret.extend_from_slice(data);
+}
+
+// 读取一段带长度前缀的数据，返回该数据和剩余部分
+fn take_prefixed(bytes: &[u8]) -> Result<(&[u8], &[u8]), KVObjectError> {
+    if bytes.len() < LEN_PREFIX_LEN {
+        return Err(KVObjectError::DeSerializeError);
+    }
+    let mut len = [0u8; LEN_PREFIX_LEN];
+    len.clone_from_slice(&bytes[..LEN_PREFIX_LEN]);
+    let end = LEN_PREFIX_LEN + u32::from_le_bytes(len) as usize;
+    if bytes.len() < end {
+        return Err(KVObjectError::DeSerializeError);
+    }
+    Ok((&bytes[LEN_PREFIX_LEN..end], &bytes[end..]))
+}
+
+// 将头部之后的数据段拆分为结构体T数据和元数据
+fn split_body_section(flags: u8, bytes: &[u8]) -> Result<(&[u8], Metadata), KVObjectError> {
+    if flags & FLAG_METADATA == 0 {
+        return Ok((bytes, Vec::new()));
+    }
+    let (body, mut rest) = take_prefixed(bytes)?;
+    let mut metadata = Vec::new();
+    while !rest.is_empty() {
+        let (key, after_key) = take_prefixed(rest)?;
+        let (value, after_value) = take_prefixed(after_key)?;
+        let key = core::str::from_utf8(key).map_err(|_| KVObjectError::DeSerializeError)?;
+        metadata.push((String::from(key), Vec::<u8>::from(value)));
+        rest = after_value;
+    }
+    Ok((body, metadata))
+}
+
+// 用证书验证签名原文的签名，默认证书和签名是未签名对象的占位值
+fn verify_signature<K: KVKeypair, H: Default + Hasher<Output = [u8; 32]>>(
+    body: &[u8],
+    cert: &K::Cert,
+    signature: &K::Sig,
+) -> Result<(), VerifyFailure> {
+    check_signature::<K>(cert, signature, || cert.verify::<H>(body, signature))
+}
+
+// 拒绝默认占位的证书和签名，再由verify完成验签
+fn check_signature<K: KVKeypair>(
+    cert: &K::Cert,
+    signature: &K::Sig,
+    verify: impl FnOnce() -> bool,
+) -> Result<(), VerifyFailure> {
+    if *cert == K::Cert::default() {
+        return Err(VerifyFailure::DefaultCert);
+    }
+    if signature.to_bytes().as_ref() == K::Sig::default().to_bytes().as_ref() {
+        return Err(VerifyFailure::DefaultSignature);
+    }
+    if !verify() {
+        return Err(VerifyFailure::BadSignature);
+    }
+    Ok(())
+}
+
+// 不组装对象，直接用DefaultHasher验证签名原文的签名，与KVObject::verfiy_kvhead一致
+// body为不含长度前缀的签名原文，长度前缀在此补上
+pub fn verify_components(
+    body: &[u8],
+    cert: &CertificateSm2,
+    sig: &SignatureSm2,
+) -> Result<(), KVObjectError> {
+    let mut preimage = Vec::<u8>::with_capacity(LEN_PREFIX_LEN + body.len());
+    push_prefixed(&mut preimage, body);
+    verify_signature::<KeyPairSm2, DefaultHasher>(&preimage, cert, sig).map_err(KVObjectError::from)
+}
+
+// 借用to_bytes数据的只读视图，不构造KVObject，适用于只按类型分发的场景
+// 证书和签名为占位值时视为None，元数据不做解析
+#[derive(Debug, Clone)]
+pub struct KVObjectRef<'a> {
+    msg_type: MsgType,
+    flags: u8,
+    cert: Option<&'a [u8]>,
+    signature: Option<&'a [u8]>,
+    body: &'a [u8],
+}
+
+impl<'a> KVObjectRef<'a> {
+    pub fn parse_ref(bytes: &'a [u8]) -> Result<KVObjectRef<'a>, KVObjectError> {
+        let msg_type = peek_msg_type(bytes).map_err(|_| KVObjectError::DeSerializeError)?;
+        let flags = bytes[FLAGS_OFFSET];
+        check_flags(flags)?;
+        if flags & FLAG_NO_CERT != 0 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+
+        let (cert, signature) = if flags & FLAG_SIGNED != 0 {
+            let cert = &bytes[CERT_OFFSET..CERT_END];
+            let signature = &bytes[SIGTURE_OFFSET..SIGTURE_END];
+            (
+                Some(cert).filter(|c| *c != CertificateSm2::default().to_bytes().as_ref()),
+                Some(signature).filter(|s| *s != SignatureSm2::default().to_bytes().as_ref()),
+            )
+        } else {
+            (None, None)
+        };
+
+        let rest = &bytes[HEAD_TOTAL_LEN..];
+        let body = if flags & FLAG_METADATA == 0 {
+            rest
+        } else {
+            take_prefixed(rest)?.0
+        };
+
+        Ok(Self {
+            msg_type,
+            flags,
+            cert,
+            signature,
+            body,
+        })
+    }
+
+    pub fn msg_type(&self) -> &MsgType {
+        &self.msg_type
+    }
+
+    pub fn cert(&self) -> Option<&'a [u8]> {
+        self.cert
+    }
+
+    pub fn signature(&self) -> Option<&'a [u8]> {
+        self.signature
+    }
+
+    // 结构体T的数据段，不含元数据
+    pub fn body_slice(&self) -> &'a [u8] {
+        self.body
+    }
+
+    // 按KVObject<T>的方式验签，结构体T只用于计算签名原文，验证后即丢弃
+    pub fn verify<T: KVBody>(&self) -> Result<(), KVObjectError> {
+        let (cert, signature) = match (self.cert, self.signature) {
+            (Some(cert), Some(signature)) => (cert, signature),
+            _ => return Err(KVObjectError::UnsignedObject),
+        };
+        let cert = CertificateSm2::from_bytes(cert)?;
+        let signature = SignatureSm2::from_bytes(signature)?;
+        let t_obj = KVObject::<T>::decode_body(self.flags, self.body)?;
+        let preimage = KVObject::<T>::signing_bytes_of(&t_obj, self.body)?;
+        verify_signature::<KeyPairSm2, DefaultHasher>(&preimage, &cert, &signature)
+            .map_err(KVObjectError::from)
+    }
+}
+
+// 已验证对象的内容标识集合，重复收到同一对象时跳过验签
+#[derive(Debug, Default)]
+pub struct VerifyCache {
+    verified: BTreeSet<[u8; 32]>,
+}
+
+impl VerifyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 仅在内容标识未命中时完整验签，验签成功后记录该标识
+    pub fn verify_cached<
+        T: KVBody,
+        K: KVKeypair,
+        H: Default + Hasher<Output = [u8; 32]>,
+        C: BodyCodec,
+    >(
+        &mut self,
+        obj: &KVObjectGeneric<T, K, H, C>,
+    ) -> Result<(), KVObjectError> {
+        let id = obj.content_id();
+        if self.verified.contains(&id) {
+            return Ok(());
+        }
+        obj.verfiy_kvhead()?;
+        self.verified.insert(id);
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.verified.clear();
+    }
+}
+
+// 从分块到达的字节流中按长度前缀切分并解析对象，不完整的帧留待后续数据
+pub struct KVObjectDecoder<
+    T: KVBody,
+    K: KVKeypair = KeyPairSm2,
+    H = DefaultHasher,
+    C = NoCompression,
+> {
+    buf: Vec<u8>,
+    max_frame_len: usize,
+    marker: PhantomData<KVObjectGeneric<T, K, H, C>>,
+}
+
+impl<T: KVBody, K: KVKeypair, H, C> Default for KVObjectDecoder<T, K, H, C> {
+    fn default() -> Self {
+        Self {
+            buf: Vec::new(),
+            max_frame_len: MAX_FRAME_LEN,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H, C> KVObjectDecoder<T, K, H, C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 指定单个帧的长度上限，默认为MAX_FRAME_LEN
+    pub fn with_max_frame_len(max_frame_len: usize) -> Self {
+        Self {
+            max_frame_len,
+            ..Self::default()
+        }
+    }
+
+    pub fn push(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    // 缓冲区中尚未组成完整帧的字节数
+    pub fn pending(&self) -> usize {
+        self.buf.len()
+    }
+}
+
+impl<T: KVBody, K: KVKeypair, H, C: BodyCodec> Iterator for KVObjectDecoder<T, K, H, C> {
+    type Item = Result<KVObjectGeneric<T, K, H, C>, KVObjectError>;
+
+    // 缓冲区中有完整帧时取出并解析，解析失败的帧同样被消耗
+    // 长度前缀超过上限时无法再定位后续帧的边界，清空缓冲区并返回错误
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.len() < FRAME_LEN_LEN {
+            return None;
+        }
+        let end = match frame_end(&self.buf, self.max_frame_len) {
+            Ok(end) => end,
+            Err(e) => {
+                self.buf.clear();
+                return Some(Err(e));
+            }
+        };
+        if self.buf.len() < end {
+            return None;
+        }
+        let frame: Vec<u8> = self.buf.drain(..end).collect();
+        Some(KVObjectGeneric::from_bytes(&frame[FRAME_LEN_LEN..]))
+    }
+}
+
+// CRC-32/IEEE，多项式0xEDB88320（反射）
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn batch_result(failed: Vec<usize>) -> Result<(), Vec<usize>> {
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crc32;
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
     }
 }
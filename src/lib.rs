@@ -1,15 +1,42 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 extern crate alloc;
 
+pub mod attr_util;
+
+pub mod cert_chain;
+
+pub mod codec;
+
+mod envelope;
+
+pub mod key_registry;
+
 pub mod kv_object;
 
+pub mod metrics;
+
 pub mod prelude;
 
 pub mod sm2;
 
+#[cfg(feature = "protobuf")]
+pub mod varint_body;
+
+// derive宏生成的代码通过此模块引用依赖，不属于公开接口
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+    pub use core::mem::size_of;
+    pub use dislog_hal::Bytes;
+}
+
+use alloc::boxed::Box;
 use core::fmt::Debug;
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum KVObjectError {
     FindTypeError,
     SerializeError,
@@ -18,4 +45,30 @@ pub enum KVObjectError {
     KVHeadVerifyError,
     KeyIndexError,
     ValueValid,
+    ValueLength {
+        expected: usize,
+        got: usize,
+    },
+    KeyPairGenError,
+    BodyLengthMismatch,
+    UnsignedObject,
+    InvalidPoint,
+    ClockSkew,
+    UntrustedIssuer,
+    ChecksumMismatch,
+    InvalidSignature,
+    TypeMismatch,
+    BadMagic,
+    LengthMismatch,
+    BodyRejected,
+    // 附带调用方标签的解析错误，便于定位出错的对象
+    Labeled {
+        label: &'static str,
+        source: Box<KVObjectError>,
+    },
+    // 批量操作中第index个对象出错
+    AtIndex {
+        index: usize,
+        source: Box<KVObjectError>,
+    },
 }
@@ -0,0 +1,82 @@
+//! 解析耗时统计。启用`metrics`特性时，`from_bytes`将头部、证书和签名、结构体T三个阶段的耗时
+//! 记录到线程局部变量；未启用时计时调用均为空操作。
+
+#[cfg(feature = "metrics")]
+use std::cell::Cell;
+#[cfg(feature = "metrics")]
+use std::time::{Duration, Instant};
+
+// 最近一次成功解析的各阶段耗时
+#[cfg(feature = "metrics")]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ParseMetrics {
+    pub header: Duration,
+    pub cert: Duration,
+    pub body: Duration,
+}
+
+#[cfg(feature = "metrics")]
+std::thread_local! {
+    static LAST_PARSE: Cell<Option<ParseMetrics>> = const { Cell::new(None) };
+}
+
+// 当前线程最近一次成功解析的耗时，尚未解析过时各字段为零
+#[cfg(feature = "metrics")]
+pub fn last_parse_metrics() -> ParseMetrics {
+    LAST_PARSE.with(|metrics| metrics.get()).unwrap_or_default()
+}
+
+// 取出当前线程最近一次成功解析的耗时并清空记录，自上次取出后未成功解析过时返回None
+#[cfg(feature = "metrics")]
+pub fn take_parse_metrics() -> Option<ParseMetrics> {
+    LAST_PARSE.with(|metrics| metrics.take())
+}
+
+pub(crate) struct ParseTimer {
+    #[cfg(feature = "metrics")]
+    last: Instant,
+    #[cfg(feature = "metrics")]
+    metrics: ParseMetrics,
+}
+
+impl ParseTimer {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(feature = "metrics")]
+            last: Instant::now(),
+            #[cfg(feature = "metrics")]
+            metrics: ParseMetrics::default(),
+        }
+    }
+
+    // 返回上一阶段结束至今的耗时
+    #[cfg(feature = "metrics")]
+    fn lap(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now - self.last;
+        self.last = now;
+        elapsed
+    }
+
+    pub(crate) fn header_done(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            self.metrics.header = self.lap();
+        }
+    }
+
+    pub(crate) fn cert_done(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            self.metrics.cert = self.lap();
+        }
+    }
+
+    pub(crate) fn body_done(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            self.metrics.body = self.lap();
+            LAST_PARSE.with(|metrics| metrics.set(Some(self.metrics)));
+        }
+    }
+}
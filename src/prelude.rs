@@ -1,16 +1,120 @@
 use crate::KVObjectError;
-use dislog_hal::Bytes;
+use alloc::vec::Vec;
+use asymmetric_crypto::prelude::{Certificate, Keypair};
+use core::fmt::Debug;
+use dislog_hal::{Bytes, Hasher};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
 pub trait AttrProxy {
     type Byte;
 
-    // 根据key读取值
+    // 根据key读取值，空key或仅含空白的key必须返回KeyIndexError
     fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError>;
 
-    // 根据key写取值
+    // 根据key写取值，空key或仅含空白的key必须返回KeyIndexError
     fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError>;
+
+    // 先拒绝空key和仅含空白的key，再调用get_key
+    fn checked_get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        if key.trim().is_empty() {
+            return Err(KVObjectError::KeyIndexError);
+        }
+        self.get_key(key)
+    }
+
+    // 先拒绝空key和仅含空白的key，再调用set_key
+    fn checked_set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        if key.trim().is_empty() {
+            return Err(KVObjectError::KeyIndexError);
+        }
+        self.set_key(key, value)
+    }
+
+    // 根据key读取值到调用者提供的缓冲区，便于重复使用
+    fn get_key_into(&self, key: &str, out: &mut Self::Byte) -> Result<(), KVObjectError> {
+        *out = self.get_key(key)?;
+        Ok(())
+    }
+
+    // 列出所有可读写的key
+    fn list_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // 根据key获取值的字节长度
+    fn key_len(&self, _key: &str) -> Result<usize, KVObjectError> {
+        Err(KVObjectError::KeyIndexError)
+    }
+
+    // 根据key获取值的字节长度而不保留值，默认读取后取长度，可覆盖以避免get_key的分配
+    fn key_byte_len(&self, key: &str) -> Result<usize, KVObjectError>
+    where
+        Self::Byte: AsRef<[u8]>,
+    {
+        Ok(self.get_key(key)?.as_ref().len())
+    }
+
+    // 批量写入前预留空间，内部带缓冲区的结构体可覆盖此方法以减少重新分配
+    fn reserve_attrs(&mut self, _additional: usize) {}
+
+    // 按顺序批量写入，写入前调用reserve_attrs，遇到错误时立即返回
+    fn set_keys(&mut self, pairs: &[(&str, Self::Byte)]) -> Result<(), KVObjectError> {
+        self.reserve_attrs(pairs.len());
+        for (key, value) in pairs {
+            self.checked_set_key(key, value)?;
+        }
+        Ok(())
+    }
+
+    // 将所有key重置为对应长度的零值
+    fn reset(&mut self) -> Result<(), KVObjectError>
+    where
+        Self::Byte: From<Vec<u8>>,
+    {
+        for key in self.list_keys() {
+            let zeroed = Self::Byte::from(alloc::vec![0u8; self.key_len(key)?]);
+            self.set_key(key, &zeroed)?;
+        }
+        Ok(())
+    }
+}
+
+// KVObjectGeneric可使用的密钥对，证书和签名按固定长度写入头部
+pub trait KVKeypair:
+    Keypair<Certificate = <Self as KVKeypair>::Cert, Signature = <Self as KVKeypair>::Sig>
+{
+    type Cert: Certificate<Signature = <Self as KVKeypair>::Sig>
+        + Bytes<Error = KVObjectError>
+        + Default
+        + Debug
+        + Clone
+        + PartialEq
+        + Serialize
+        + for<'de> Deserialize<'de>;
+
+    type Sig: Bytes<Error = KVObjectError>
+        + Default
+        + Debug
+        + Clone
+        + Serialize
+        + for<'de> Deserialize<'de>;
+
+    // 证书序列化后的长度
+    const CERT_LEN: usize;
+
+    // 签名序列化后的长度
+    const SIGTURE_LEN: usize;
+}
+
+// 结构体T序列化数据的编码方式，编码在签名之前进行，签名覆盖编码后的数据
+pub trait BodyCodec {
+    // 为true时编码即原样输出，序列化时不设置压缩标志
+    const IDENTITY: bool = false;
+
+    fn compress(data: &[u8]) -> Vec<u8>;
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, KVObjectError>;
 }
 
 pub trait KValueObject: Serialize + for<'de> Deserialize<'de> + AttrProxy + Bytes {
@@ -20,6 +124,9 @@ pub trait KValueObject: Serialize + for<'de> Deserialize<'de> + AttrProxy + Byte
 
     type Certificate: asymmetric_crypto::prelude::Certificate<Signature = Self::Signature>;
 
+    // 签名和验签共用的摘要算法，避免两端选用不同的算法
+    type Hasher: Default + Hasher<Output = [u8; 32]>;
+
     fn fill_kvhead(
         &mut self,
         keypair: &Self::KeyPair,
@@ -1,5 +1,7 @@
+use crate::prelude::KVKeypair;
 use crate::KVObjectError;
 use asymmetric_crypto::hasher::sha3::Sha3;
+use asymmetric_crypto::hasher::sm3::Sm3;
 use asymmetric_crypto::keypair::Keypair;
 use asymmetric_crypto::prelude::Certificate;
 use asymmetric_crypto::{signature, CryptoError, NewU8129, NewU864};
@@ -8,8 +10,41 @@ use dislog_hal_sm2::NewU833;
 use rand::RngCore;
 use serde::{Deserialize, Serialize, Serializer, Deserializer};
 use hex::{ToHex, FromHex};
+use libsm::sm2::ecc::EccCtx;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::hash::Hash;
+use core::ops::Deref;
+
+// 依赖库中的定长字节包装只实现了AsRef<[u8]>，此包装补充Deref以便直接切片
+pub struct ByteArray<B: AsRef<[u8]>>(pub B);
+
+impl<B: AsRef<[u8]>> ByteArray<B> {
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for ByteArray<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> core::fmt::Debug for ByteArray<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.as_ref().fmt(f)
+    }
+}
+
+impl<B: AsRef<[u8]>> Deref for ByteArray<B> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
 
 #[derive(Default, Debug, Clone)]
 pub struct KeyPairSm2(
@@ -58,12 +93,81 @@ impl asymmetric_crypto::prelude::Keypair for KeyPairSm2 {
     }
 }
 
+impl KVKeypair for KeyPairSm2 {
+    type Cert = CertificateSm2;
+
+    type Sig = SignatureSm2;
+
+    const CERT_LEN: usize = 33;
+
+    const SIGTURE_LEN: usize = 64;
+}
+
+impl KeyPairSm2 {
+    // 用于派生子密钥的32字节链码
+    pub fn chain_code(&self) -> [u8; 32] {
+        let mut ret = [0u8; 32];
+        ret.clone_from_slice(self.0.get_code().as_ref());
+        ret
+    }
+
+    // 强化派生子密钥，由链码、私钥和序号生成子密钥种子
+    pub fn derive_child(&self, index: u32) -> Result<KeyPairSm2, KVObjectError> {
+        let mut hasher = Sm3::default();
+        hasher.update(self.0.get_code().as_ref());
+        hasher.update([0u8].as_ref());
+        hasher.update(self.0.get_secret_key().to_bytes().as_ref());
+        hasher.update(index.to_be_bytes().as_ref());
+
+        <Self as asymmetric_crypto::prelude::Keypair>::generate_from_seed(hasher.finalize())
+            .map_err(|_| KVObjectError::KeyPairGenError)
+    }
+
+    // 仅保存私钥和公钥的紧凑格式。种子和链码不保存，恢复后的密钥对无法派生子密钥
+    pub fn to_bytes_minimal(&self) -> [u8; 65] {
+        let mut ret = [0u8; 65];
+        ret[0..32].clone_from_slice(self.0.get_secret_key().to_bytes().as_ref());
+        ret[32..65].clone_from_slice(self.0.get_public_key().to_bytes().as_ref());
+
+        ret
+    }
+
+    pub fn from_bytes_minimal(bytes: &[u8]) -> Result<Self, KVObjectError> {
+        if bytes.len() != 65 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let pri_key = Scalar::<dislog_hal_sm2::ScalarInner>::from_bytes(&bytes[0..32])
+            .map_err(|_| KVObjectError::DeSerializeError)?;
+        let pub_key = Point::<dislog_hal_sm2::PointInner>::from_bytes(&bytes[32..65])
+            .map_err(|_| KVObjectError::DeSerializeError)?;
+        Ok(Self(Keypair::<_, _, _, _>::new(
+            [0u8; 32], pub_key, pri_key, [0u8; 32],
+        )))
+    }
+
+    // 导入其他系统的32字节私钥，公钥由私钥计算。种子和链码填零，导入的密钥对可直接签名，但无法派生子密钥
+    pub fn from_secret_scalar(scalar: &[u8; 32]) -> Result<Self, KVObjectError> {
+        if scalar.iter().all(|b| *b == 0) {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let pri_key = Scalar::<dislog_hal_sm2::ScalarInner>::from_bytes(&scalar[..])
+            .map_err(|_| KVObjectError::DeSerializeError)?;
+        let pub_key = &Point::<dislog_hal_sm2::PointInner>::one() * &pri_key;
+        Ok(Self(Keypair::<_, _, _, _>::new(
+            [0u8; 32], pub_key, pri_key, [0u8; 32],
+        )))
+    }
+}
+
 impl Bytes for KeyPairSm2 {
-    type BytesType = NewU8129;
+    type BytesType = ByteArray<NewU8129>;
 
     type Error = KVObjectError;
 
     fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != 129 {
+            return Err(KVObjectError::DeSerializeError);
+        }
         let mut seed = [0u8; 32];
         for i in 0..32 {
             seed.as_mut()[i] = bytes[i];
@@ -86,9 +190,17 @@ impl Bytes for KeyPairSm2 {
         ret[0..32].clone_from_slice(self.0.get_seed().as_ref());
         ret[32..64].clone_from_slice(self.0.get_secret_key().to_bytes().as_ref());
         ret[64..97].clone_from_slice(self.0.get_public_key().to_bytes().as_ref());
-        ret[97..129].clone_from_slice(self.0.get_seed().as_ref());
+        ret[97..129].clone_from_slice(self.0.get_code().as_ref());
+
+        ByteArray(NewU8129(ret))
+    }
+}
 
-        NewU8129(ret)
+impl TryFrom<&[u8]> for KeyPairSm2 {
+    type Error = KVObjectError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
     }
 }
 
@@ -109,15 +221,34 @@ impl<'de> Deserialize<'de> for KeyPairSm2 {
         let d_str = String::deserialize(deserializer)
             .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))?;
         let d_byte = Vec::<u8>::from_hex(d_str)
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))?;
+            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex")))?;
+        if d_byte.len() != 129 {
+            return Err(serde::de::Error::custom(format_args!(
+                "wrong length: expected 129 bytes, got {}",
+                d_byte.len()
+            )));
+        }
         KeyPairSm2::from_bytes(d_byte.as_slice())
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))
+            .map_err(|_| serde::de::Error::custom(format_args!("invalid point/scalar")))
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Clone)]
 pub struct CertificateSm2(<KeyPairSm2 as asymmetric_crypto::prelude::Keypair>::Public);
 
+// 只输出证书Sm3指纹的前4字节
+impl core::fmt::Debug for CertificateSm2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut hasher = Sm3::default();
+        hasher.update(&self.to_bytes()[..]);
+        write!(
+            f,
+            "Certificate(fp={}…)",
+            (&hasher.finalize()[0..4]).encode_hex_upper::<String>()
+        )
+    }
+}
+
 impl Certificate for CertificateSm2 {
     type Signature = SignatureSm2;
 
@@ -132,6 +263,15 @@ impl Certificate for CertificateSm2 {
     }
 }
 
+impl Eq for CertificateSm2 {}
+
+// 按压缩编码计算哈希，与PartialEq一致
+impl Hash for CertificateSm2 {
+    fn hash<HS: core::hash::Hasher>(&self, state: &mut HS) {
+        self.to_bytes().as_ref().hash(state);
+    }
+}
+
 impl Default for CertificateSm2 {
     fn default() -> Self {
         Self(<KeyPairSm2 as asymmetric_crypto::prelude::Keypair>::Public::default())
@@ -139,18 +279,185 @@ impl Default for CertificateSm2 {
 }
 
 impl Bytes for CertificateSm2 {
-    type BytesType = NewU833;
+    type BytesType = ByteArray<NewU833>;
 
     type Error = KVObjectError;
 
     fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        // 长度须恰好为压缩公钥长度，多余的字节视为错误
+        if bytes.len() != <KeyPairSm2 as KVKeypair>::CERT_LEN {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        // 只接受压缩编码，首字节须为0x02或0x03
+        if bytes[0] != 0x02 && bytes[0] != 0x03 {
+            return Err(KVObjectError::InvalidPoint);
+        }
         let a = <KeyPairSm2 as asymmetric_crypto::prelude::Keypair>::Public::from_bytes(bytes)
             .map_err(|_| KVObjectError::DeSerializeError)?;
         Ok(Self(a))
     }
 
     fn to_bytes(&self) -> Self::BytesType {
-        self.0.to_bytes()
+        ByteArray(self.0.to_bytes())
+    }
+}
+
+impl TryFrom<&[u8]> for CertificateSm2 {
+    type Error = KVObjectError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl CertificateSm2 {
+    // 是否为未签名对象使用的默认证书占位值
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // 验证任意消息的签名，如登录挑战值，无需引入Certificate trait
+    pub fn verify_message<H: Default + Hasher<Output = [u8; 32]>>(
+        &self,
+        msg: &[u8],
+        sig: &SignatureSm2,
+    ) -> bool {
+        <Self as Certificate>::verify::<H>(self, msg, sig)
+    }
+
+    // 解压公钥，返回大端序的仿射坐标 (x, y)，默认占位证书等不在曲线上的点返回InvalidPoint
+    pub fn coordinates(&self) -> Result<([u8; 32], [u8; 32]), KVObjectError> {
+        let uncompressed = self.to_uncompressed()?;
+        let mut x = [0u8; 32];
+        let mut y = [0u8; 32];
+        x.clone_from_slice(&uncompressed[1..33]);
+        y.clone_from_slice(&uncompressed[33..65]);
+        Ok((x, y))
+    }
+
+    // 未压缩编码 0x04 || x || y，用于与只接受该格式的外部系统交互
+    pub fn to_uncompressed(&self) -> Result<[u8; 65], KVObjectError> {
+        let uncompressed = reencode_point(self.to_bytes().as_ref(), false)?;
+        if uncompressed.len() != 65 {
+            return Err(KVObjectError::InvalidPoint);
+        }
+        let mut ret = [0u8; 65];
+        ret.clone_from_slice(&uncompressed);
+        Ok(ret)
+    }
+
+    // 解析未压缩编码，坐标须在曲线上：由x解压出的点须与给出的y一致
+    pub fn from_uncompressed(bytes: &[u8]) -> Result<Self, KVObjectError> {
+        if bytes.len() != 65 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        if bytes[0] != 0x04 {
+            return Err(KVObjectError::InvalidPoint);
+        }
+        let cert = Self::from_bytes(&reencode_point(bytes, true)?)?;
+        if cert.to_uncompressed()?[..] != bytes[..] {
+            return Err(KVObjectError::InvalidPoint);
+        }
+        Ok(cert)
+    }
+
+    // 计算SM2用户身份杂凑值 ZA = SM3(ENTL || ID || a || b || xG || yG || xA || yA)
+    pub fn za(&self, id: &[u8]) -> Result<[u8; 32], KVObjectError> {
+        // ENTL为ID的比特长度，占两个字节
+        if id.len() > 0x1fff {
+            return Err(KVObjectError::ValueLength {
+                expected: 0x1fff,
+                got: id.len(),
+            });
+        }
+        let (x, y) = self.coordinates()?;
+        let mut hasher = Sm3::default();
+        hasher.update(&((id.len() * 8) as u16).to_be_bytes()[..]);
+        hasher.update(id);
+        hasher.update(&SM2_A[..]);
+        hasher.update(&SM2_B[..]);
+        hasher.update(&SM2_GX[..]);
+        hasher.update(&SM2_GY[..]);
+        hasher.update(&x[..]);
+        hasher.update(&y[..]);
+        Ok(hasher.finalize())
+    }
+}
+
+// 由libsm解码压缩或未压缩编码的公钥点，再按compress指定的格式重新编码
+fn reencode_point(bytes: &[u8], compress: bool) -> Result<Vec<u8>, KVObjectError> {
+    let ctx = EccCtx::new();
+    let point = ctx
+        .bytes_to_point(bytes)
+        .map_err(|_| KVObjectError::InvalidPoint)?;
+    ctx.point_to_bytes(&point, compress)
+        .map_err(|_| KVObjectError::InvalidPoint)
+}
+
+// SM2标准中未指定用户ID时使用的默认值
+pub const DEFAULT_USER_ID: &[u8] = b"1234567812345678";
+
+// SM2标准曲线参数a、b和基点G的坐标（大端序），用于计算ZA
+const SM2_A: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfc,
+];
+const SM2_B: [u8; 32] = [
+    0x28, 0xe9, 0xfa, 0x9e, 0x9d, 0x9f, 0x5e, 0x34, 0x4d, 0x5a, 0x9e, 0x4b, 0xcf, 0x65, 0x09, 0xa7,
+    0xf3, 0x97, 0x89, 0xf5, 0x15, 0xab, 0x8f, 0x92, 0xdd, 0xbc, 0xbd, 0x41, 0x4d, 0x94, 0x0e, 0x93,
+];
+const SM2_GX: [u8; 32] = [
+    0x32, 0xc4, 0xae, 0x2c, 0x1f, 0x19, 0x81, 0x19, 0x5f, 0x99, 0x04, 0x46, 0x6a, 0x39, 0xc9, 0x94,
+    0x8f, 0xe3, 0x0b, 0xbf, 0xf2, 0x66, 0x0b, 0xe1, 0x71, 0x5a, 0x45, 0x89, 0x33, 0x4c, 0x74, 0xc7,
+];
+const SM2_GY: [u8; 32] = [
+    0xbc, 0x37, 0x36, 0xa2, 0xf4, 0xf6, 0x77, 0x9c, 0x59, 0xbd, 0xce, 0xe3, 0x6b, 0x69, 0x21, 0x53,
+    0xd0, 0xa9, 0x87, 0x7c, 0xc6, 0x2a, 0x47, 0x40, 0x02, 0xdf, 0x32, 0xe5, 0x21, 0x39, 0xf0, 0xa0,
+];
+
+// 可读格式（如JSON）中证书和签名为大写hex字符串，二进制格式（如bincode）中为原始字节
+fn serialize_raw<SE: Serializer>(bytes: &[u8], serializer: SE) -> Result<SE::Ok, SE::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&bytes.encode_hex_upper::<String>())
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+struct RawBytesVisitor;
+
+impl<'de> serde::de::Visitor<'de> for RawBytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Vec::from(v))
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut ret = Vec::new();
+        while let Some(b) = seq.next_element::<u8>()? {
+            ret.push(b);
+        }
+        Ok(ret)
+    }
+}
+
+fn deserialize_raw<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        let d_str = String::deserialize(deserializer)
+            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))?;
+        Vec::<u8>::from_hex(d_str)
+            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex")))
+    } else {
+        deserializer.deserialize_byte_buf(RawBytesVisitor)
     }
 }
 
@@ -159,7 +466,7 @@ impl Serialize for CertificateSm2 {
     where
         SE: Serializer,
     {
-        serializer.serialize_str(&self.to_bytes().encode_hex_upper::<String>())
+        serialize_raw(self.to_bytes().as_ref(), serializer)
     }
 }
 
@@ -168,18 +475,34 @@ impl<'de> Deserialize<'de> for CertificateSm2 {
     where
         D: Deserializer<'de>,
     {
-        let d_str = String::deserialize(deserializer)
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))?;
-        let d_byte = Vec::<u8>::from_hex(d_str)
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))?;
+        let d_byte = deserialize_raw(deserializer)?;
+        if d_byte.len() != 33 {
+            return Err(serde::de::Error::custom(format_args!(
+                "wrong length: expected 33 bytes, got {}",
+                d_byte.len()
+            )));
+        }
         CertificateSm2::from_bytes(d_byte.as_slice())
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))
+            .map_err(|_| serde::de::Error::custom(format_args!("invalid point")))
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct SignatureSm2(pub signature::sm2::Signature<dislog_hal_sm2::ScalarInner>);
 
+// 只输出r和s的前4字节，避免日志被完整签名刷屏
+impl core::fmt::Debug for SignatureSm2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let bytes = self.to_bytes();
+        write!(
+            f,
+            "Signature(r={}…, s={}…)",
+            (&bytes[0..4]).encode_hex_upper::<String>(),
+            (&bytes[32..36]).encode_hex_upper::<String>()
+        )
+    }
+}
+
 impl Default for SignatureSm2 {
     fn default() -> Self {
         Self(signature::sm2::Signature::<dislog_hal_sm2::ScalarInner>::default())
@@ -187,16 +510,32 @@ impl Default for SignatureSm2 {
 }
 
 impl Bytes for SignatureSm2 {
-    type BytesType = NewU864;
+    type BytesType = ByteArray<NewU864>;
 
     type Error = KVObjectError;
 
     fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != 64 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        // SM2签名的r和s均须非零
+        let (r, s) = bytes.split_at(32);
+        if r.iter().all(|b| *b == 0) || s.iter().all(|b| *b == 0) {
+            return Err(KVObjectError::InvalidSignature);
+        }
         Ok(Self(signature::sm2::Signature::<dislog_hal_sm2::ScalarInner>::from_bytes(bytes).map_err(|_| KVObjectError::DeSerializeError)?))
     }
 
     fn to_bytes(&self) -> Self::BytesType {
-        self.0.to_bytes()
+        ByteArray(self.0.to_bytes())
+    }
+}
+
+impl TryFrom<&[u8]> for SignatureSm2 {
+    type Error = KVObjectError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
     }
 }
 
@@ -205,7 +544,7 @@ impl Serialize for SignatureSm2 {
     where
         SE: Serializer,
     {
-        serializer.serialize_str(&self.to_bytes().encode_hex_upper::<String>())
+        serialize_raw(self.to_bytes().as_ref(), serializer)
     }
 }
 
@@ -214,12 +553,40 @@ impl<'de> Deserialize<'de> for SignatureSm2 {
     where
         D: Deserializer<'de>,
     {
-        let d_str = String::deserialize(deserializer)
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))?;
-        let d_byte = Vec::<u8>::from_hex(d_str)
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))?;
+        let d_byte = deserialize_raw(deserializer)?;
+        if d_byte.len() != 64 {
+            return Err(serde::de::Error::custom(format_args!(
+                "wrong length: expected 64 bytes, got {}",
+                d_byte.len()
+            )));
+        }
         SignatureSm2::from_bytes(d_byte.as_slice())
-            .map_err(|_| serde::de::Error::custom(format_args!("invalid hex string")))
+            .map_err(|_| serde::de::Error::custom(format_args!("invalid scalar")))
+    }
+}
+
+// 多次验签共享的SM2上下文
+// 依赖库的signature::sm2::sm2_verify每次调用都重新计算基点倍点，没有接收预计算表的接口。
+// 上游增加形如sm2_verify_with_table(hasher, table, public_key, signature)的函数后，
+// 在new中构造基点倍点表，verify改为调用该函数即可，调用方无需改动
+#[derive(Debug, Default, Clone)]
+pub struct Sm2Context {
+    _private: (),
+}
+
+impl Sm2Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 用证书验证msg的签名，与CertificateSm2::verify结果一致
+    pub fn verify<H: Default + Hasher<Output = [u8; 32]>>(
+        &self,
+        cert: &CertificateSm2,
+        msg: &[u8],
+        sig: &SignatureSm2,
+    ) -> bool {
+        cert.verify::<H>(msg, sig)
     }
 }
 
@@ -229,6 +596,20 @@ mod tests {
     use asymmetric_crypto::hasher::sm3::Sm3;
     use asymmetric_crypto::prelude::Certificate;
 
+    // GM/T 0003.5签名示例中的公钥（私钥为3945208F…4DF7C5B8）
+    const SAMPLE_PUBKEY_COMPRESSED: [u8; 33] = [
+        0x03, 0x09, 0xf9, 0xdf, 0x31, 0x1e, 0x54, 0x21, 0xa1, 0x50, 0xdd, 0x7d, 0x16, 0x1e, 0x4b,
+        0xc5, 0xc6, 0x72, 0x17, 0x9f, 0xad, 0x18, 0x33, 0xfc, 0x07, 0x6b, 0xb0, 0x8f, 0xf3, 0x56,
+        0xf3, 0x50, 0x20,
+    ];
+    const SAMPLE_PUBKEY_UNCOMPRESSED: [u8; 65] = [
+        0x04, 0x09, 0xf9, 0xdf, 0x31, 0x1e, 0x54, 0x21, 0xa1, 0x50, 0xdd, 0x7d, 0x16, 0x1e, 0x4b,
+        0xc5, 0xc6, 0x72, 0x17, 0x9f, 0xad, 0x18, 0x33, 0xfc, 0x07, 0x6b, 0xb0, 0x8f, 0xf3, 0x56,
+        0xf3, 0x50, 0x20, 0xcc, 0xea, 0x49, 0x0c, 0xe2, 0x67, 0x75, 0xa5, 0x2d, 0xc6, 0xea, 0x71,
+        0x8c, 0xc1, 0xaa, 0x60, 0x0a, 0xed, 0x05, 0xfb, 0xf3, 0x5e, 0x08, 0x4a, 0x66, 0x32, 0xf6,
+        0x07, 0x2d, 0xa9, 0xad, 0x13,
+    ];
+
     #[test]
     fn it_works() {
         use asymmetric_crypto::prelude::Keypair;
@@ -251,4 +632,360 @@ mod tests {
         let ans = cert_sm2.verify::<Sm3>(&data_b[..], &sig_info);
         assert_eq!(ans, true);
     }
+
+    #[test]
+    fn test_derive_child() {
+        use asymmetric_crypto::prelude::Keypair;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let master: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+        let child_0 = master.derive_child(0).unwrap();
+        let child_0_again = master.derive_child(0).unwrap();
+        let child_1 = master.derive_child(1).unwrap();
+
+        assert_eq!(child_0.get_certificate(), child_0_again.get_certificate());
+        assert_ne!(child_0.get_certificate(), child_1.get_certificate());
+        assert_ne!(child_0.get_certificate(), master.get_certificate());
+    }
+
+    #[test]
+    fn test_minimal_bytes() {
+        use asymmetric_crypto::prelude::Keypair;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+        let minimal = keypair_sm2.to_bytes_minimal();
+        let keypair_1 = KeyPairSm2::from_bytes_minimal(&minimal[..]).unwrap();
+
+        assert_eq!(keypair_sm2.get_certificate(), keypair_1.get_certificate());
+        assert_eq!(&minimal[..], &keypair_1.to_bytes_minimal()[..]);
+
+        let sig_info = keypair_1.sign::<Sm3, _>(b"minimal", &mut rng).unwrap();
+        assert!(keypair_sm2
+            .get_certificate()
+            .verify::<Sm3>(b"minimal", &sig_info));
+
+        assert!(KeyPairSm2::from_bytes_minimal(&minimal[..64]).is_err());
+    }
+
+    #[test]
+    fn test_coordinates() {
+        use super::CertificateSm2;
+        use crate::KVObjectError;
+        use dislog_hal::Bytes;
+
+        // SM2基点G的压缩编码
+        let mut g = alloc::vec![0x02u8];
+        g.extend_from_slice(&super::SM2_GX);
+        let cert = CertificateSm2::from_bytes(&g).unwrap();
+        assert_eq!(cert.coordinates().unwrap(), (super::SM2_GX, super::SM2_GY));
+
+        // GM/T 0003.5示例中的公钥，y为奇数
+        let cert = CertificateSm2::from_bytes(&SAMPLE_PUBKEY_COMPRESSED[..]).unwrap();
+        let (x, y) = cert.coordinates().unwrap();
+        assert_eq!(&x[..], &SAMPLE_PUBKEY_UNCOMPRESSED[1..33]);
+        assert_eq!(&y[..], &SAMPLE_PUBKEY_UNCOMPRESSED[33..]);
+
+        assert_eq!(
+            CertificateSm2::default().coordinates().unwrap_err(),
+            KVObjectError::InvalidPoint
+        );
+    }
+
+    #[test]
+    fn test_from_secret_scalar() {
+        use asymmetric_crypto::prelude::Keypair;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+        let mut scalar = [0u8; 32];
+        scalar.clone_from_slice(&keypair_sm2.to_bytes_minimal()[0..32]);
+        let imported = KeyPairSm2::from_secret_scalar(&scalar).unwrap();
+        assert_eq!(imported.get_certificate(), keypair_sm2.get_certificate());
+
+        let sig_info = imported.sign::<Sm3, _>(b"imported", &mut rng).unwrap();
+        assert!(imported
+            .get_certificate()
+            .verify::<Sm3>(b"imported", &sig_info));
+
+        assert!(KeyPairSm2::from_secret_scalar(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_try_from() {
+        use super::{CertificateSm2, SignatureSm2};
+        use crate::KVObjectError;
+        use asymmetric_crypto::prelude::Keypair;
+        use core::convert::{TryFrom, TryInto};
+        use dislog_hal::Bytes;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+        let cert_sm2 = keypair_sm2.get_certificate();
+        let sig_info = keypair_sm2.sign::<Sm3, _>(b"try_from", &mut rng).unwrap();
+
+        let keypair_1 = KeyPairSm2::try_from(keypair_sm2.to_bytes().as_ref()).unwrap();
+        assert_eq!(keypair_1.get_certificate(), cert_sm2);
+        let cert_1: CertificateSm2 = cert_sm2.to_bytes().as_ref().try_into().unwrap();
+        assert_eq!(cert_1, cert_sm2);
+        let sig_1: SignatureSm2 = sig_info.to_bytes().as_ref().try_into().unwrap();
+        assert!(cert_1.verify::<Sm3>(b"try_from", &sig_1));
+
+        assert_eq!(
+            KeyPairSm2::try_from(&keypair_sm2.to_bytes().as_ref()[..128]).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+        assert_eq!(
+            CertificateSm2::try_from(&cert_sm2.to_bytes().as_ref()[..32]).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+        assert_eq!(
+            SignatureSm2::try_from(&sig_info.to_bytes().as_ref()[..63]).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+    }
+
+    #[test]
+    fn test_cert_exact_len() {
+        use super::CertificateSm2;
+        use crate::KVObjectError;
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+        let cert_sm2 = keypair_sm2.get_certificate();
+
+        let mut bytes = cert_sm2.to_bytes().as_ref().to_vec();
+        assert_eq!(CertificateSm2::from_bytes(&bytes).unwrap(), cert_sm2);
+        bytes.push(0);
+        assert_eq!(bytes.len(), 34);
+        assert_eq!(
+            CertificateSm2::from_bytes(&bytes).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+    }
+
+    #[test]
+    fn test_cert_compressed_prefix() {
+        use super::CertificateSm2;
+        use crate::KVObjectError;
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+        // 基点G的未压缩编码 0x04 || x || y
+        let mut uncompressed = alloc::vec![0x04u8];
+        uncompressed.extend_from_slice(&super::SM2_GX);
+        uncompressed.extend_from_slice(&super::SM2_GY);
+        assert!(CertificateSm2::from_bytes(&uncompressed).is_err());
+        assert_eq!(
+            CertificateSm2::from_bytes(&uncompressed[..33]).unwrap_err(),
+            KVObjectError::InvalidPoint
+        );
+
+        let mut zero_prefix = keypair_sm2.get_certificate().to_bytes().as_ref().to_vec();
+        zero_prefix[0] = 0x00;
+        assert_eq!(
+            CertificateSm2::from_bytes(&zero_prefix).unwrap_err(),
+            KVObjectError::InvalidPoint
+        );
+    }
+
+    #[test]
+    fn test_uncompressed() {
+        use super::CertificateSm2;
+        use crate::KVObjectError;
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use rand::thread_rng;
+
+        // 已知公钥的65字节编码与33字节压缩编码互相转换
+        let cert = CertificateSm2::from_uncompressed(&SAMPLE_PUBKEY_UNCOMPRESSED[..]).unwrap();
+        assert_eq!(cert.to_bytes().as_ref(), &SAMPLE_PUBKEY_COMPRESSED[..]);
+        assert_eq!(
+            &cert.to_uncompressed().unwrap()[..],
+            &SAMPLE_PUBKEY_UNCOMPRESSED[..]
+        );
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+        let cert = keypair_sm2.get_certificate();
+        let uncompressed = cert.to_uncompressed().unwrap();
+        assert_eq!(
+            CertificateSm2::from_uncompressed(&uncompressed).unwrap(),
+            cert
+        );
+
+        // y不在曲线上
+        let mut off_curve = SAMPLE_PUBKEY_UNCOMPRESSED;
+        off_curve[64] ^= 0x01;
+        assert_eq!(
+            CertificateSm2::from_uncompressed(&off_curve).unwrap_err(),
+            KVObjectError::InvalidPoint
+        );
+
+        assert_eq!(
+            CertificateSm2::from_uncompressed(&SAMPLE_PUBKEY_UNCOMPRESSED[..64]).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+        let mut bad_prefix = SAMPLE_PUBKEY_UNCOMPRESSED;
+        bad_prefix[0] = 0x02;
+        assert_eq!(
+            CertificateSm2::from_uncompressed(&bad_prefix).unwrap_err(),
+            KVObjectError::InvalidPoint
+        );
+    }
+
+    #[test]
+    fn test_zero_signature() {
+        use super::SignatureSm2;
+        use crate::KVObjectError;
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use rand::thread_rng;
+
+        assert_eq!(
+            SignatureSm2::from_bytes(&[0u8; 64]).unwrap_err(),
+            KVObjectError::InvalidSignature
+        );
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+        let sig_info = keypair_sm2.sign::<Sm3, _>(b"zero", &mut rng).unwrap();
+        let mut sig_bytes = [0u8; 64];
+        sig_bytes.clone_from_slice(sig_info.to_bytes().as_ref());
+        for b in sig_bytes[32..].iter_mut() {
+            *b = 0;
+        }
+        assert_eq!(
+            SignatureSm2::from_bytes(&sig_bytes).unwrap_err(),
+            KVObjectError::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn test_chain_code() {
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+        let mut bytes = [0u8; 129];
+        bytes.clone_from_slice(keypair_sm2.to_bytes().as_ref());
+        for (i, b) in bytes[97..129].iter_mut().enumerate() {
+            *b = 0xa0 ^ i as u8;
+        }
+        let mut code = [0u8; 32];
+        code.clone_from_slice(&bytes[97..129]);
+        assert_ne!(&code[..], &bytes[0..32]);
+
+        let imported = KeyPairSm2::from_bytes(&bytes).unwrap();
+        assert_eq!(imported.chain_code(), code);
+        assert_eq!(imported.to_bytes().as_ref(), &bytes[..]);
+    }
+
+    #[test]
+    fn test_compact_debug() {
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use hex::ToHex;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+        let sig_info = keypair_sm2.sign::<Sm3, _>(b"debug", &mut rng).unwrap();
+
+        let sig_debug = alloc::format!("{:?}", sig_info);
+        let r_prefix = (&sig_info.to_bytes()[0..4]).encode_hex_upper::<alloc::string::String>();
+        assert!(sig_debug.len() < 40);
+        assert!(sig_debug.starts_with(&alloc::format!("Signature(r={}…", r_prefix)));
+
+        let cert_debug = alloc::format!("{:?}", keypair_sm2.get_certificate());
+        assert!(cert_debug.len() < 30);
+        assert!(cert_debug.starts_with("Certificate(fp="));
+    }
+
+    #[test]
+    fn test_za() {
+        use super::CertificateSm2;
+        use crate::KVObjectError;
+        use alloc::string::String;
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use hex::ToHex;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+        let cert = keypair_sm2.get_certificate();
+
+        let za = cert.za(super::DEFAULT_USER_ID).unwrap();
+        assert_ne!(za, cert.za(b"alice@example.com").unwrap());
+
+        // GM/T 0003.5示例公钥在默认ID和示例ID下的ZA
+        let sample = CertificateSm2::from_bytes(&SAMPLE_PUBKEY_COMPRESSED[..]).unwrap();
+        assert_eq!(
+            sample
+                .za(super::DEFAULT_USER_ID)
+                .unwrap()
+                .encode_hex::<String>(),
+            "b2e14c5c79c6df5b85f4fe7ed8db7a262b9da7e07ccb0ea9f4747b8ccda8a4f3"
+        );
+        assert_eq!(
+            sample
+                .za(b"ALICE123@YAHOO.COM")
+                .unwrap()
+                .encode_hex::<String>(),
+            "26db4bc1839bd22e97e1dab667ec5e0a730d5e16521398b4435c576a93afd7ed"
+        );
+        assert_eq!(
+            cert.za(&[0u8; 0x2000][..]).unwrap_err(),
+            KVObjectError::ValueLength {
+                expected: 0x1fff,
+                got: 0x2000
+            }
+        );
+    }
+
+    #[test]
+    fn test_byte_array_slice() {
+        use asymmetric_crypto::prelude::Keypair;
+        use dislog_hal::Bytes;
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+        let cert_sm2 = keypair_sm2.get_certificate();
+        let sig_info = keypair_sm2.sign::<Sm3, _>(b"slice", &mut rng).unwrap();
+
+        let keypair_bytes = keypair_sm2.to_bytes();
+        assert_eq!(keypair_bytes.len(), 129);
+        assert_eq!(&keypair_bytes[64..97], &cert_sm2.to_bytes()[..]);
+
+        let cert_bytes = cert_sm2.to_bytes();
+        assert_eq!(cert_bytes.len(), 33);
+        assert!(cert_bytes[0] == 0x02 || cert_bytes[0] == 0x03);
+
+        let sig_bytes = sig_info.to_bytes();
+        assert_eq!(sig_bytes.len(), 64);
+        assert_eq!(&sig_bytes[..], sig_bytes.as_ref());
+        assert_eq!(
+            &sig_bytes.into_inner().as_ref()[..32],
+            &sig_info.to_bytes()[..32]
+        );
+    }
 }
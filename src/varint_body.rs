@@ -0,0 +1,162 @@
+//! 以protobuf varint编码的结构体T，便于其他语言的服务解析。
+//! 每个字段编码为`(field_number << 3 | wire_type, value)`，目前只支持varint类型。
+
+use crate::attr_util::{read_u64_le, write_u64_le};
+use crate::kv_object::KVBody;
+use crate::prelude::AttrProxy;
+use crate::KVObjectError;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use dislog_hal::Bytes;
+use serde::{Deserialize, Serialize};
+
+pub const WIRE_TYPE_VARINT: u64 = 0;
+
+// protobuf允许的最大字段编号
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+pub fn encode_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+// 返回解码的值和占用的字节数
+pub fn decode_varint(bytes: &[u8]) -> Result<(u64, usize), KVObjectError> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        let low = u64::from(byte & 0x7f);
+        if i == 9 && low > 1 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        value |= low << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(KVObjectError::DeSerializeError)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VarintBody {
+    fields: BTreeMap<u32, u64>,
+}
+
+impl VarintBody {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, field_number: u32) -> Option<u64> {
+        self.fields.get(&field_number).copied()
+    }
+
+    pub fn set(&mut self, field_number: u32, value: u64) -> Result<(), KVObjectError> {
+        if field_number == 0 || field_number > MAX_FIELD_NUMBER {
+            return Err(KVObjectError::KeyIndexError);
+        }
+        self.fields.insert(field_number, value);
+        Ok(())
+    }
+
+    fn parse_key(key: &str) -> Result<u32, KVObjectError> {
+        key.parse::<u32>().map_err(|_| KVObjectError::KeyIndexError)
+    }
+}
+
+impl Bytes for VarintBody {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let mut ret = Self::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let (tag, len) = decode_varint(&bytes[offset..])?;
+            offset += len;
+            if tag & 0x07 != WIRE_TYPE_VARINT || tag >> 3 > u64::from(MAX_FIELD_NUMBER) {
+                return Err(KVObjectError::DeSerializeError);
+            }
+            let (value, len) = decode_varint(&bytes[offset..])?;
+            offset += len;
+            ret.set((tag >> 3) as u32, value)
+                .map_err(|_| KVObjectError::DeSerializeError)?;
+        }
+        Ok(ret)
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        let mut ret = Vec::<u8>::new();
+        for (field_number, value) in self.fields.iter() {
+            encode_varint(u64::from(*field_number) << 3 | WIRE_TYPE_VARINT, &mut ret);
+            encode_varint(*value, &mut ret);
+        }
+        ret
+    }
+}
+
+impl AttrProxy for VarintBody {
+    type Byte = Vec<u8>;
+
+    // 根据字段编号读取值，值为u64小端序
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        self.get(Self::parse_key(key)?)
+            .map(write_u64_le)
+            .ok_or(KVObjectError::KeyIndexError)
+    }
+
+    // 根据字段编号写入值，值为u64小端序
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        self.set(Self::parse_key(key)?, read_u64_le(value)?)
+    }
+
+    fn key_len(&self, key: &str) -> Result<usize, KVObjectError> {
+        self.get_key(key).map(|value| value.len())
+    }
+}
+
+impl KVBody for VarintBody {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint() {
+        let mut out = Vec::new();
+        encode_varint(300, &mut out);
+        assert_eq!(out, [0xac, 0x02]);
+        assert_eq!(decode_varint(&out).unwrap(), (300, 2));
+
+        let mut out = Vec::new();
+        encode_varint(u64::MAX, &mut out);
+        assert_eq!(out.len(), 10);
+        assert_eq!(decode_varint(&out).unwrap(), (u64::MAX, 10));
+
+        assert!(decode_varint(&[0x80, 0x80]).is_err());
+    }
+
+    #[test]
+    fn test_varint_body() {
+        let mut body = VarintBody::new();
+        body.set(1, 150).unwrap();
+        body.set(2, 1).unwrap();
+
+        // 字段1 = 150，字段2 = 1
+        let encoded = [0x08, 0x96, 0x01, 0x10, 0x01];
+        assert_eq!(body.to_bytes(), encoded);
+        assert_eq!(VarintBody::from_bytes(&encoded).unwrap(), body);
+
+        assert_eq!(body.get_key("1").unwrap(), write_u64_le(150));
+        body.set_key("2", &write_u64_le(7)).unwrap();
+        assert_eq!(body.get(2), Some(7));
+        assert_eq!(body.get_key("3").unwrap_err(), KVObjectError::KeyIndexError);
+        assert_eq!(body.get_key("x").unwrap_err(), KVObjectError::KeyIndexError);
+
+        // wire type 2 (length-delimited) 不支持
+        assert!(VarintBody::from_bytes(&[0x0a, 0x01, 0x01]).is_err());
+    }
+}
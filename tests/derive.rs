@@ -0,0 +1,95 @@
+#![cfg(feature = "derive")]
+
+use asymmetric_crypto::prelude::Keypair;
+use dislog_hal::Bytes;
+use kv_object::kv_object::{KVBody, KVObject, MsgType};
+use kv_object::prelude::{AttrProxy, KValueObject};
+use kv_object::sm2::KeyPairSm2;
+use kv_object::KVObjectError;
+use rand::thread_rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, KVBody)]
+pub struct DerivedPoint {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, KVBody)]
+pub struct DerivedMixed {
+    pub flag: u8,
+    pub amount: u64,
+    pub delta: i16,
+}
+
+// 与tests/test.rs中手写的TestPoint编码一致
+fn hand_written_point(x: i32, y: i32) -> Vec<u8> {
+    let mut ret = Vec::<u8>::new();
+    ret.extend_from_slice(&x.to_le_bytes()[..]);
+    ret.extend_from_slice(&y.to_le_bytes()[..]);
+    ret
+}
+
+#[test]
+fn test_derive_bytes() {
+    let point = DerivedPoint { x: 3, y: -5 };
+    assert_eq!(point.to_bytes(), hand_written_point(3, -5));
+    assert_eq!(
+        DerivedPoint::from_bytes(&hand_written_point(3, -5)).unwrap(),
+        point
+    );
+    assert_eq!(
+        DerivedPoint::from_bytes(&[0u8; 7]).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+    assert_eq!(DerivedPoint::size_hint(), Some(8));
+
+    let mixed = DerivedMixed {
+        flag: 1,
+        amount: 0x0102_0304_0506_0708,
+        delta: -2,
+    };
+    let bytes = mixed.to_bytes();
+    assert_eq!(bytes, [1, 8, 7, 6, 5, 4, 3, 2, 1, 0xfe, 0xff]);
+    assert_eq!(DerivedMixed::from_bytes(&bytes).unwrap(), mixed);
+    assert_eq!(DerivedMixed::size_hint(), Some(11));
+}
+
+#[test]
+fn test_derive_attr_proxy() {
+    let mut point = DerivedPoint { x: 3, y: 5 };
+    assert_eq!(point.list_keys(), &["x", "y"]);
+    assert_eq!(point.key_len("y"), Ok(4));
+    assert_eq!(point.get_key("x").unwrap(), vec![3, 0, 0, 0]);
+    assert_eq!(
+        point.get_key("z").unwrap_err(),
+        KVObjectError::KeyIndexError
+    );
+
+    point.set_key("y", &vec![9, 0, 0, 0]).unwrap();
+    assert_eq!(point, DerivedPoint { x: 3, y: 9 });
+    assert_eq!(
+        point.set_key("x", &vec![9, 0, 0]).unwrap_err(),
+        KVObjectError::ValueLength {
+            expected: 4,
+            got: 3
+        }
+    );
+
+    point.reset().unwrap();
+    assert_eq!(point, DerivedPoint { x: 0, y: 0 });
+}
+
+#[test]
+fn test_derive_kvobject() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point =
+        KVObject::<DerivedPoint>::new(MsgType::IssueQuotaRequest, DerivedPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let point_1 = KVObject::<DerivedPoint>::from_bytes(&point.to_bytes()).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(point_1.get_body(), &DerivedPoint { x: 3, y: 5 });
+}
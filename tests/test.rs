@@ -1,13 +1,27 @@
-use asymmetric_crypto::prelude::Keypair;
+use asymmetric_crypto::hasher::sha3::Sha3;
+use asymmetric_crypto::hasher::sm3::Sm3;
+use asymmetric_crypto::prelude::{Certificate, Keypair};
+use asymmetric_crypto::CryptoError;
 use core::fmt::Debug;
-use dislog_hal::Bytes;
-use kv_object::kv_object::MsgType;
-use kv_object::kv_object::{get_msgtpye, KVBody, KVObject};
-use kv_object::prelude::{AttrProxy, KValueObject};
-use kv_object::sm2::KeyPairSm2;
+use dislog_hal::{Bytes, Hasher};
+use kv_object::attr_util::{read_u64_le, write_u64_le};
+use kv_object::cert_chain::CertChainBuilder;
+use kv_object::key_registry::{key_id, KeyRegistry, KEY_ID_LEN};
+use kv_object::kv_object::{
+    count_framed, get_msgtpye, merkle_root, peek_msg_type, resign_all, serialize_batch,
+    verify_batch, verify_components, DefaultHasher, KVBody, KVObject, KVObjectDecoder,
+    KVObjectGeneric, KVObjectRef, KVObjectSha3, VerifyCache, VerifyFailure,
+};
+use kv_object::kv_object::{
+    MsgType, CERT_LEN, FLAG_COMPRESSED, FORMAT_VERSION, FRAME_LEN_LEN, HEAD_TOTAL_LEN, MAGIC,
+    MAX_FRAME_LEN, VERSION_SHIFT,
+};
+use kv_object::prelude::{AttrProxy, BodyCodec, KVKeypair, KValueObject};
+use kv_object::sm2::{CertificateSm2, KeyPairSm2, SignatureSm2, Sm2Context, DEFAULT_USER_ID};
 use kv_object::KVObjectError;
-use rand::thread_rng;
+use rand::{thread_rng, RngCore};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TestPoint {
@@ -61,8 +75,12 @@ impl AttrProxy for TestPoint {
 
     // 根据key取值
     fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
-        if value.len() != 4 {
-            return Err(KVObjectError::ValueValid);
+        let width = self.key_width(key)?;
+        if value.len() != width {
+            return Err(KVObjectError::ValueLength {
+                expected: width,
+                got: value.len(),
+            });
         }
 
         let mut a_ = [0u8; 4];
@@ -78,12 +96,129 @@ impl AttrProxy for TestPoint {
 
         return Ok(());
     }
+
+    fn get_key_into(&self, key: &str, out: &mut Self::Byte) -> Result<(), KVObjectError> {
+        let value = match key {
+            "x" => self.x.to_le_bytes(),
+            "y" => self.y.to_le_bytes(),
+            _ => return Err(KVObjectError::KeyIndexError),
+        };
+        out.clear();
+        out.extend_from_slice(&value[..]);
+        Ok(())
+    }
+
+    fn list_keys(&self) -> &'static [&'static str] {
+        &["x", "y"]
+    }
+
+    fn key_len(&self, key: &str) -> Result<usize, KVObjectError> {
+        match key {
+            "x" | "y" => Ok(4),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn key_byte_len(&self, key: &str) -> Result<usize, KVObjectError> {
+        match key {
+            "x" | "y" => Ok(4),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+}
+
+impl KVBody for TestPoint {
+    const DEFAULT_MSG_TYPE: MsgType = MsgType::IssueQuotaRequest;
+
+    fn size_hint() -> Option<usize> {
+        Some(8)
+    }
+
+    fn byte_len(&self) -> usize {
+        8
+    }
+
+    fn key_width(&self, key: &str) -> Result<usize, KVObjectError> {
+        match key {
+            "x" | "y" => Ok(4),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn key_alias(key: &str) -> &str {
+        match key {
+            "abscissa" => "x",
+            _ => key,
+        }
+    }
+
+    fn validate(&self) -> Result<(), KVObjectError> {
+        if self.x < 0 || self.y < 0 {
+            return Err(KVObjectError::ValueValid);
+        }
+        Ok(())
+    }
+}
+
+// 只解析前4字节，丢弃之后的数据，模拟旧版本的结构体
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestPrefix {
+    pub x: i32,
+}
+
+impl Bytes for TestPrefix {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < 4 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let mut x_ = [0u8; 4];
+        x_.clone_from_slice(&bytes[..4]);
+        Ok(Self {
+            x: i32::from_le_bytes(x_),
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        Vec::<u8>::from(&self.x.to_le_bytes()[..])
+    }
+}
+
+impl AttrProxy for TestPrefix {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        match key {
+            "x" => Ok(Vec::<u8>::from(&self.x.to_le_bytes()[..])),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        match key {
+            "x" => {
+                let mut x_ = [0u8; 4];
+                if value.len() != 4 {
+                    return Err(KVObjectError::ValueValid);
+                }
+                x_.clone_from_slice(&value[..4]);
+                self.x = i32::from_le_bytes(x_);
+            }
+            _ => return Err(KVObjectError::KeyIndexError),
+        }
+        Ok(())
+    }
 }
 
-impl KVBody for TestPoint {}
+impl KVBody for TestPrefix {}
 
 type NewPoint = KVObject<TestPoint>;
 
+type NewPointSha3 = KVObjectSha3<TestPoint>;
+
 #[test]
 fn test_json_object() {
     let point = TestPoint { x: 1, y: 2 };
@@ -196,3 +331,3054 @@ fn test_kvwrapper() {
 
     assert!(point_1.verfiy_kvhead().is_err(), false);
 }
+
+#[test]
+fn test_verify_batch() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut points = Vec::<NewPoint>::new();
+    for i in 0..4 {
+        let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: i, y: i });
+        point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+        points.push(point);
+    }
+
+    assert!(verify_batch(&points).is_ok());
+
+    points[1]
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    points[3]
+        .set_key("x", &Vec::<u8>::from([9, 0, 0, 0].as_ref()))
+        .unwrap();
+
+    assert_eq!(verify_batch(&points), Err(vec![1, 3]));
+}
+
+#[test]
+fn test_unsigned_roundtrip() {
+    let point = NewPoint::new(MsgType::DigitalCurrency, TestPoint { x: 3, y: 5 });
+
+    let unsigned_bytes = point.to_bytes();
+    assert_eq!(
+        unsigned_bytes[kv_object::kv_object::FLAGS_OFFSET],
+        FORMAT_VERSION << VERSION_SHIFT
+    );
+
+    let point_1 = NewPoint::from_bytes(&unsigned_bytes).unwrap();
+    assert!(point_1.get_cert().is_none());
+    assert!(point_1.get_signature().is_none());
+    assert_eq!(&TestPoint { x: 3, y: 5 }, point_1.get_body());
+}
+
+#[test]
+fn test_unknown_flags() {
+    use kv_object::kv_object::FLAGS_OFFSET;
+
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::Transaction, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let sign_bytes = point.to_bytes();
+    let compact = point.to_bytes_no_cert();
+
+    // 版本号之外未定义的高位
+    for unknown in [0x20u8, 0x40, 0x80].iter() {
+        let mut bytes = sign_bytes.clone();
+        bytes[FLAGS_OFFSET] |= unknown;
+        assert_eq!(
+            NewPoint::from_bytes(&bytes).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+        assert_eq!(
+            KVObjectRef::parse_ref(&bytes).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+
+        let mut bytes = compact.clone();
+        bytes[FLAGS_OFFSET] |= unknown;
+        assert_eq!(
+            NewPoint::from_bytes_with_cert(&bytes, keypair_sm2.get_certificate()).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+    }
+}
+
+#[test]
+fn test_into_from_parts() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::Transaction, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let sign_bytes = point.to_bytes();
+
+    let (msg_type, cert, signature, body) = point.into_parts();
+    assert_eq!(msg_type, MsgType::Transaction);
+    assert_eq!(cert, Some(keypair_sm2.get_certificate()));
+    assert_eq!(body, TestPoint { x: 3, y: 5 });
+
+    let point_1 = NewPoint::from_parts(msg_type, cert, signature, body);
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(sign_bytes, point_1.to_bytes());
+}
+
+#[test]
+fn test_json_unsigned_state() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::DigitalCurrency, TestPoint { x: 3, y: 5 });
+
+    let serialized = serde_json::to_string(&point).unwrap();
+    assert!(serialized.contains("\"cert\":null"));
+    assert!(serialized.contains("\"signature\":null"));
+
+    let point_1: NewPoint = serde_json::from_str(&serialized).unwrap();
+    assert!(point_1.get_cert().is_none());
+    assert!(point_1.get_signature().is_none());
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let serialized = serde_json::to_string(&point).unwrap();
+    let point_2: NewPoint = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(point.get_cert(), point_2.get_cert());
+    assert!(point_2.verfiy_kvhead().is_ok());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_debug_dump() {
+    let point = NewPoint::new(MsgType::QuotaControlField, TestPoint { x: 3, y: 5 });
+
+    let dump = point.debug_dump();
+    println!("{}", dump);
+
+    assert!(dump.contains("QuotaControlField"));
+    assert!(dump.contains("x: 03000000"));
+    assert!(dump.contains("y: 05000000"));
+}
+
+#[test]
+fn test_body_length_mismatch() {
+    let point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+
+    let mut point_bytes = point.to_bytes();
+    point_bytes.truncate(point_bytes.len() - 2);
+
+    assert_eq!(
+        NewPoint::from_bytes(&point_bytes).unwrap_err(),
+        KVObjectError::BodyLengthMismatch
+    );
+}
+
+#[test]
+fn test_reset() {
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+
+    point.reset().unwrap();
+
+    assert_eq!(
+        Vec::<u8>::from([0, 0, 0, 0].as_ref()),
+        point.get_key("x").unwrap()
+    );
+    assert_eq!(&TestPoint { x: 0, y: 0 }, point.get_body());
+}
+
+#[test]
+fn test_hex_roundtrip() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let point_hex = point.to_hex();
+    assert_eq!(point_hex, point_hex.to_uppercase());
+
+    let point_1 = NewPoint::from_hex(&point_hex).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(point.to_bytes(), point_1.to_bytes());
+
+    assert_eq!(
+        NewPoint::from_hex("not hex").unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_verify_and_cert() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert!(point.verify_and_cert().is_err());
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(
+        point.verify_and_cert().unwrap(),
+        keypair_sm2.get_certificate()
+    );
+
+    point
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert!(point.verify_and_cert().is_err());
+}
+
+#[test]
+fn test_peek_msg_type() {
+    let point = NewPoint::new(MsgType::QuotaRecycleReceipt, TestPoint { x: 3, y: 5 });
+    let point_bytes = point.to_bytes();
+
+    assert_eq!(
+        peek_msg_type(&point_bytes).unwrap(),
+        MsgType::QuotaRecycleReceipt
+    );
+
+    let truncated = [0x04u8];
+    assert_eq!(
+        get_msgtpye(&truncated).unwrap(),
+        MsgType::QuotaRecycleReceipt
+    );
+    assert_eq!(
+        peek_msg_type(&truncated).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_get_key_into() {
+    let point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+
+    let mut buf = Vec::<u8>::new();
+    point.get_key_into("x", &mut buf).unwrap();
+    assert_eq!(buf, point.get_key("x").unwrap());
+
+    point.get_key_into("y", &mut buf).unwrap();
+    assert_eq!(buf, point.get_key("y").unwrap());
+
+    assert_eq!(
+        point.get_key_into("z", &mut buf).unwrap_err(),
+        KVObjectError::KeyIndexError
+    );
+}
+
+#[test]
+fn test_cert_fingerprint() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_other: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert!(point.cert_fingerprint_sm3().is_none());
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let mut point_1 = NewPoint::new(MsgType::Transaction, TestPoint { x: 7, y: 9 });
+    point_1.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let mut point_2 = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point_2.fill_kvhead(&keypair_other, &mut rng).unwrap();
+
+    assert!(point.cert_fingerprint_sm3().is_some());
+    assert_eq!(point.cert_fingerprint_sm3(), point_1.cert_fingerprint_sm3());
+    assert_ne!(point.cert_fingerprint_sm3(), point_2.cert_fingerprint_sm3());
+}
+
+#[test]
+fn test_value_length_error() {
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+
+    assert_eq!(
+        point
+            .set_key("x", &Vec::<u8>::from([7, 0, 0].as_ref()))
+            .unwrap_err(),
+        KVObjectError::ValueLength {
+            expected: 4,
+            got: 3
+        }
+    );
+}
+
+#[test]
+fn test_unsigned_object_error() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(
+        point.verfiy_kvhead().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+
+    let point_default = NewPoint::from_parts(
+        MsgType::IssueQuotaRequest,
+        Some(CertificateSm2::default()),
+        Some(SignatureSm2::default()),
+        TestPoint { x: 3, y: 5 },
+    );
+    assert_eq!(
+        point_default.verfiy_kvhead().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+
+    let mut point_bad = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point_bad.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    point_bad
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert_eq!(
+        point_bad.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_attrs() {
+    let point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+
+    let attrs: Vec<(&str, Vec<u8>)> = point.attrs().collect::<Result<_, _>>().unwrap();
+    assert_eq!(
+        attrs,
+        vec![
+            ("x", Vec::<u8>::from([3, 0, 0, 0].as_ref())),
+            ("y", Vec::<u8>::from([5, 0, 0, 0].as_ref())),
+        ]
+    );
+}
+
+#[test]
+fn test_sign_hasher() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point =
+        KVObject::<TestPoint, Sm3>::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(point.verfiy_kvhead().is_ok());
+
+    let mut point_sha3 = NewPointSha3::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point_sha3.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(point_sha3.verfiy_kvhead().is_ok());
+    assert!(NewPointSha3::from_bytes(&point_sha3.to_bytes())
+        .unwrap()
+        .verfiy_kvhead()
+        .is_ok());
+
+    // 用另一种摘要算法验签应失败
+    let (msg_type, cert, signature, t_obj) = point.into_parts();
+    let cross_sha3 = NewPointSha3::from_parts(msg_type, cert, signature, t_obj);
+    assert_eq!(
+        cross_sha3.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    let (msg_type, cert, signature, t_obj) = point_sha3.into_parts();
+    let cross_sm3 = KVObject::<TestPoint, Sm3>::from_parts(msg_type, cert, signature, t_obj);
+    assert_eq!(
+        cross_sm3.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_assemble() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let (msg_type, cert, signature, t_obj) = point.into_parts();
+    let point_1 = NewPoint::assemble(
+        msg_type,
+        &t_obj.to_bytes(),
+        cert.unwrap(),
+        signature.unwrap(),
+    )
+    .unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(&t_obj, point_1.get_body());
+
+    assert_eq!(
+        NewPoint::assemble(
+            MsgType::IssueQuotaRequest,
+            &[0u8; 3],
+            keypair_sm2.get_certificate(),
+            SignatureSm2::default(),
+        )
+        .unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_raw_body() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    // 新版本的结构体在x之后多出y和z，签名覆盖整个结构体
+    let mut point =
+        KVObject::<TestPointV2>::new(MsgType::IssueQuotaRequest, TestPointV2 { x: 3, y: 5, z: 9 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let sign_bytes = point.to_bytes();
+
+    let mut prefix = KVObject::<TestPrefix>::from_bytes(&sign_bytes).unwrap();
+    assert_eq!(&TestPrefix { x: 3 }, prefix.get_body());
+    assert_eq!(
+        prefix.get_raw_body(),
+        Some(&sign_bytes[sign_bytes.len() - 12..])
+    );
+    assert_eq!(prefix.to_bytes(), sign_bytes);
+    assert!(prefix.verfiy_kvhead().is_ok());
+
+    prefix
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert_eq!(prefix.get_raw_body(), None);
+    assert_eq!(prefix.to_bytes().len(), sign_bytes.len() - 8);
+}
+
+#[test]
+fn test_msg_type_groups() {
+    let cases = [
+        (MsgType::IssueQuotaRequest, true, false, false),
+        (MsgType::QuotaControlField, false, false, false),
+        (MsgType::DigitalCurrency, false, false, true),
+        (MsgType::QuotaRecycleReceipt, false, true, false),
+        (MsgType::ConvertQoutaRequest, true, false, false),
+        (MsgType::Transaction, false, false, false),
+    ];
+
+    for (msg_type, request, receipt, currency) in cases.iter() {
+        assert_eq!(msg_type.is_request(), *request, "{:?}", msg_type);
+        assert_eq!(msg_type.is_receipt(), *receipt, "{:?}", msg_type);
+        assert_eq!(msg_type.is_currency(), *currency, "{:?}", msg_type);
+    }
+}
+
+#[test]
+fn test_clone_unsigned() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_other: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let mut point_1 = point.clone_unsigned();
+    assert_eq!(point.get_body(), point_1.get_body());
+    assert!(point_1.get_cert().is_none());
+    assert!(point_1.get_signature().is_none());
+    assert_eq!(
+        point_1.verfiy_kvhead().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+
+    point_1.fill_kvhead(&keypair_other, &mut rng).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(
+        point_1.get_cert().as_ref(),
+        Some(&keypair_other.get_certificate())
+    );
+}
+
+#[test]
+fn test_without_signature() {
+    let mut rng = thread_rng();
+    let keypair_a: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_b: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point_a = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    let unsigned_bytes = point_a.to_bytes();
+    let mut point_b = point_a.clone();
+    point_a.fill_kvhead(&keypair_a, &mut rng).unwrap();
+    point_b.fill_kvhead(&keypair_b, &mut rng).unwrap();
+    assert_ne!(point_a.to_bytes(), point_b.to_bytes());
+
+    let stripped = point_a.without_signature();
+    assert!(stripped.get_cert().is_none());
+    assert!(stripped.get_signature().is_none());
+    assert_eq!(stripped.to_bytes(), point_b.without_signature().to_bytes());
+    assert_eq!(stripped.to_bytes(), unsigned_bytes);
+    assert_eq!(
+        stripped.content_id(),
+        point_b.without_signature().content_id()
+    );
+    assert!(point_a.verfiy_kvhead().is_ok());
+}
+
+#[test]
+fn test_default_cert_reparse() {
+    let point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert!(CertificateSm2::default().is_default());
+
+    let mut bytes = point.to_bytes();
+    let point_1 = NewPoint::from_bytes(&bytes).unwrap();
+    assert!(point_1.get_cert().is_none());
+
+    // 即使标志位声明已签名，默认证书也解析为None
+    bytes[1] |= 0x01;
+    let point_2 = NewPoint::from_bytes(&bytes).unwrap();
+    assert!(point_2.get_cert().is_none());
+    assert_eq!(
+        point_2.verfiy_kvhead().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    assert!(!keypair_sm2.get_certificate().is_default());
+}
+
+// 仅用于测试的密钥对，签名为证书和消息的摘要，不具备安全性
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StubCertificate([u8; 16]);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StubSignature([u8; 32]);
+
+#[derive(Debug, Clone)]
+pub struct StubKeyPair([u8; 16]);
+
+fn stub_digest<H: Default + Hasher<Output = [u8; 32]>>(cert: &[u8], msg: &[u8]) -> [u8; 32] {
+    let mut hasher = H::default();
+    hasher.update(cert);
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+impl Bytes for StubCertificate {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != 16 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let mut ret = [0u8; 16];
+        ret.clone_from_slice(bytes);
+        Ok(Self(ret))
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        Vec::<u8>::from(&self.0[..])
+    }
+}
+
+impl Bytes for StubSignature {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != 32 {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let mut ret = [0u8; 32];
+        ret.clone_from_slice(bytes);
+        Ok(Self(ret))
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        Vec::<u8>::from(&self.0[..])
+    }
+}
+
+impl Certificate for StubCertificate {
+    type Signature = StubSignature;
+
+    fn verify<H: Default + Hasher<Output = [u8; 32]> + Hasher>(
+        &self,
+        msg: &[u8],
+        signature: &Self::Signature,
+    ) -> bool {
+        stub_digest::<H>(&self.0, msg) == signature.0
+    }
+}
+
+impl Keypair for StubKeyPair {
+    type Seed = [u8; 16];
+
+    type Secret = [u8; 16];
+
+    type Public = [u8; 16];
+
+    type Code = [u8; 16];
+
+    type Signature = StubSignature;
+
+    type Certificate = StubCertificate;
+
+    fn generate<R: RngCore>(rng: &mut R) -> Result<Self, CryptoError> {
+        let mut seed = [0u8; 16];
+        rng.fill_bytes(&mut seed);
+        Self::generate_from_seed(seed)
+    }
+
+    fn generate_from_seed(seed: Self::Seed) -> Result<Self, CryptoError> {
+        Ok(Self(seed))
+    }
+
+    fn sign<H: Default + Hasher<Output = [u8; 32]> + Hasher, R: RngCore>(
+        &self,
+        msg: &[u8],
+        _rng: &mut R,
+    ) -> Result<Self::Signature, CryptoError> {
+        Ok(StubSignature(stub_digest::<H>(&self.0, msg)))
+    }
+
+    fn get_certificate(&self) -> Self::Certificate {
+        StubCertificate(self.0)
+    }
+}
+
+impl KVKeypair for StubKeyPair {
+    type Cert = StubCertificate;
+
+    type Sig = StubSignature;
+
+    const CERT_LEN: usize = 16;
+
+    const SIGTURE_LEN: usize = 32;
+}
+
+type StubPoint = KVObjectGeneric<TestPoint, StubKeyPair>;
+
+#[test]
+fn test_generic_keypair() {
+    let mut rng = thread_rng();
+    let keypair = StubKeyPair::generate(&mut rng).unwrap();
+
+    let mut point = StubPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair, &mut rng).unwrap();
+    assert!(point.verfiy_kvhead().is_ok());
+
+    let sign_bytes = point.to_bytes();
+    assert_eq!(sign_bytes.len(), 1 + 1 + 16 + 32 + 8);
+
+    let mut point_1 = StubPoint::from_bytes(&sign_bytes).unwrap();
+    assert_eq!(point_1.get_cert(), &Some(keypair.get_certificate()));
+    assert!(point_1.verfiy_kvhead().is_ok());
+
+    point_1
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert_eq!(
+        point_1.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestStamped {
+    pub timestamp: u64,
+}
+
+impl Bytes for TestStamped {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            timestamp: read_u64_le(bytes).map_err(|_| KVObjectError::DeSerializeError)?,
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        write_u64_le(self.timestamp)
+    }
+}
+
+impl AttrProxy for TestStamped {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        match key {
+            "timestamp" => Ok(write_u64_le(self.timestamp)),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        match key {
+            "timestamp" => self.timestamp = read_u64_le(value)?,
+            _ => return Err(KVObjectError::KeyIndexError),
+        }
+        Ok(())
+    }
+}
+
+impl KVBody for TestStamped {
+    fn timestamp(&self) -> Option<u64> {
+        Some(self.timestamp)
+    }
+
+    fn declared_type(&self) -> Option<MsgType> {
+        Some(MsgType::Transaction)
+    }
+}
+
+#[test]
+fn test_verify_with_clock() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut stamped =
+        KVObject::<TestStamped>::new(MsgType::Transaction, TestStamped { timestamp: 1_000 });
+    stamped.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    assert!(stamped.verify_with_clock(1_000, 0).is_ok());
+    assert!(stamped.verify_with_clock(1_030, 30).is_ok());
+    assert!(stamped.verify_with_clock(970, 30).is_ok());
+
+    // 时间戳过旧
+    assert_eq!(
+        stamped.verify_with_clock(1_031, 30).unwrap_err(),
+        KVObjectError::ClockSkew
+    );
+    // 时间戳超前
+    assert_eq!(
+        stamped.verify_with_clock(969, 30).unwrap_err(),
+        KVObjectError::ClockSkew
+    );
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(
+        point.verify_with_clock(1_000, 30).unwrap_err(),
+        KVObjectError::ClockSkew
+    );
+}
+
+#[test]
+fn test_cert_hash_map() {
+    use std::collections::HashMap;
+
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_other: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut by_issuer: HashMap<CertificateSm2, Vec<NewPoint>> = HashMap::new();
+    for (keypair, x) in [(&keypair_sm2, 1), (&keypair_sm2, 2), (&keypair_other, 3)].iter() {
+        let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: *x, y: 5 });
+        point.fill_kvhead(*keypair, &mut rng).unwrap();
+        by_issuer
+            .entry(point.get_cert().clone().unwrap())
+            .or_default()
+            .push(point);
+    }
+
+    assert_eq!(by_issuer.len(), 2);
+    assert_eq!(by_issuer[&keypair_sm2.get_certificate()].len(), 2);
+    assert_eq!(by_issuer[&keypair_other.get_certificate()].len(), 1);
+}
+
+static COUNTING_HASHERS: AtomicUsize = AtomicUsize::new(0);
+
+// 统计创建次数的Sm3，用于确认是否执行了验签
+pub struct CountingSm3(Sm3);
+
+impl Default for CountingSm3 {
+    fn default() -> Self {
+        COUNTING_HASHERS.fetch_add(1, Ordering::SeqCst);
+        Self(Sm3::default())
+    }
+}
+
+impl Hasher for CountingSm3 {
+    const SIZE: usize = Sm3::SIZE;
+
+    type Output = [u8; 32];
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.0.finalize()
+    }
+}
+
+#[test]
+fn test_verify_cache() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = KVObject::<TestPoint, CountingSm3>::new(
+        MsgType::IssueQuotaRequest,
+        TestPoint { x: 3, y: 5 },
+    );
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let mut cache = VerifyCache::new();
+    let before = COUNTING_HASHERS.load(Ordering::SeqCst);
+    assert!(cache.verify_cached(&point).is_ok());
+    let after_first = COUNTING_HASHERS.load(Ordering::SeqCst);
+    assert!(after_first > before);
+
+    // 命中缓存，不再验签
+    assert!(cache.verify_cached(&point).is_ok());
+    assert_eq!(COUNTING_HASHERS.load(Ordering::SeqCst), after_first);
+
+    // 验签失败的对象不进入缓存
+    let mut point_bad = point.clone();
+    point_bad
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert_eq!(
+        cache.verify_cached(&point_bad).unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+    assert_eq!(
+        cache.verify_cached(&point_bad).unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+    assert_ne!(point.content_id(), point_bad.content_id());
+}
+
+#[test]
+fn test_try_from_bytes() {
+    use std::convert::{TryFrom, TryInto};
+
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let sign_bytes = point.to_bytes();
+
+    let point_1: NewPoint = sign_bytes.as_slice().try_into().unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+
+    assert_eq!(
+        NewPoint::try_from(&sign_bytes[..sign_bytes.len() - 1]).unwrap_err(),
+        KVObjectError::BodyLengthMismatch
+    );
+    assert_eq!(
+        NewPoint::try_from(&sign_bytes[..10]).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_verify_against_any() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_other: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_third: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let trusted = [
+        keypair_other.get_certificate(),
+        keypair_sm2.get_certificate(),
+    ];
+    assert_eq!(point.verify_against_any(&trusted), Ok(1));
+
+    assert_eq!(
+        point
+            .verify_against_any(&[keypair_third.get_certificate()])
+            .unwrap_err(),
+        KVObjectError::UntrustedIssuer
+    );
+
+    point
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert_eq!(
+        point.verify_against_any(&trusted).unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+// 只有x受签名保护，y可在签名后修改
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestPartial(pub TestPoint);
+
+impl Bytes for TestPartial {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self(TestPoint::from_bytes(bytes)?))
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        self.0.to_bytes()
+    }
+}
+
+impl AttrProxy for TestPartial {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        self.0.get_key(key)
+    }
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        self.0.set_key(key, value)
+    }
+
+    fn list_keys(&self) -> &'static [&'static str] {
+        self.0.list_keys()
+    }
+}
+
+impl KVBody for TestPartial {
+    fn signed_keys() -> &'static [&'static str] {
+        &["x"]
+    }
+}
+
+#[test]
+fn test_signed_keys() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = KVObject::<TestPartial>::new(
+        MsgType::IssueQuotaRequest,
+        TestPartial(TestPoint { x: 3, y: 5 }),
+    );
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    // y不在签名范围内，修改后签名仍然有效
+    point
+        .set_key("y", &Vec::<u8>::from([9, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert!(!point.is_signature_stale());
+    assert!(point.verfiy_kvhead().is_ok());
+    let point_1 = KVObject::<TestPartial>::from_bytes(&point.to_bytes()).unwrap();
+    assert_eq!(&TestPartial(TestPoint { x: 3, y: 9 }), point_1.get_body());
+    assert!(point_1.verfiy_kvhead().is_ok());
+
+    // 修改x后证书和签名保留，签名标记为失效
+    point
+        .set_key("x", &Vec::<u8>::from([7, 0, 0, 0].as_ref()))
+        .unwrap();
+    assert!(point.is_signature_stale());
+    assert_eq!(point.get_cert(), &Some(keypair_sm2.get_certificate()));
+    assert!(point.get_signature().is_some());
+    assert_eq!(
+        point.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_no_cert_roundtrip() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_other: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let compact = point.to_bytes_no_cert();
+    assert_eq!(compact.len(), point.to_bytes().len() - 33);
+    assert_eq!(
+        NewPoint::from_bytes(&compact).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+
+    let point_1 = NewPoint::from_bytes_with_cert(&compact, keypair_sm2.get_certificate()).unwrap();
+    assert_eq!(point.get_body(), point_1.get_body());
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(point_1.to_bytes(), point.to_bytes());
+
+    let point_2 =
+        NewPoint::from_bytes_with_cert(&compact, keypair_other.get_certificate()).unwrap();
+    assert_eq!(
+        point_2.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    assert_eq!(
+        NewPoint::from_bytes_with_cert(&point.to_bytes(), keypair_sm2.get_certificate())
+            .unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_validate() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(point.validate().unwrap_err(), KVObjectError::UnsignedObject);
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(point.validate().is_ok());
+
+    let (msg_type, cert, signature, _) = point.into_parts();
+    let point_bad = NewPoint::from_parts(msg_type, cert, signature, TestPoint { x: 7, y: 5 });
+    assert_eq!(
+        point_bad.validate().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    let mut point_negative = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: -3, y: 5 });
+    point_negative.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(point_negative.verfiy_kvhead().is_ok());
+    assert_eq!(
+        point_negative.validate().unwrap_err(),
+        KVObjectError::ValueValid
+    );
+}
+
+#[test]
+fn test_deserialize_error_message() {
+    let err = serde_json::from_str::<CertificateSm2>("\"02AB\"").unwrap_err();
+    assert!(err.to_string().contains("length"), "{}", err);
+
+    let err = serde_json::from_str::<SignatureSm2>("\"ZZ\"").unwrap_err();
+    assert!(err.to_string().contains("invalid hex"), "{}", err);
+
+    let err = serde_json::from_str::<KeyPairSm2>("\"00\"").unwrap_err();
+    assert!(err.to_string().contains("length"), "{}", err);
+}
+
+#[test]
+fn test_crc_roundtrip() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let mut stored = point.to_bytes_with_crc();
+    assert_eq!(stored.len(), point.to_bytes().len() + 4);
+
+    let point_1 = NewPoint::from_bytes_with_crc(&stored).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+
+    // 篡改签名覆盖的x，在验签前即被CRC发现
+    let offset = stored.len() - 4 - 8;
+    stored[offset] ^= 0x01;
+    assert_eq!(
+        NewPoint::from_bytes_with_crc(&stored).unwrap_err(),
+        KVObjectError::ChecksumMismatch
+    );
+
+    assert_eq!(
+        NewPoint::from_bytes_with_crc(&stored[..3]).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_verify_and_digest() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let body = TestStamped { timestamp: 1_000 };
+    let mut stamped = KVObject::<TestStamped>::new(MsgType::Transaction, body.clone());
+    stamped.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let mut hasher = DefaultHasher::default();
+    hasher.update(&(body.to_bytes().len() as u32).to_le_bytes()[..]);
+    hasher.update(&body.to_bytes()[..]);
+    assert_eq!(stamped.verify_and_digest(), Ok(hasher.finalize()));
+
+    // 摘要随对象的哈希算法H变化
+    let mut stamped_sha3 = KVObjectSha3::<TestStamped>::new(MsgType::Transaction, body.clone());
+    stamped_sha3.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let mut hasher = Sha3::default();
+    hasher.update(&(body.to_bytes().len() as u32).to_le_bytes()[..]);
+    hasher.update(&body.to_bytes()[..]);
+    assert_eq!(stamped_sha3.verify_and_digest(), Ok(hasher.finalize()));
+
+    let (msg_type, cert, signature, _) = stamped.into_parts();
+    let stamped_bad = KVObject::<TestStamped>::from_parts(
+        msg_type,
+        cert,
+        signature,
+        TestStamped { timestamp: 1_001 },
+    );
+    assert_eq!(
+        stamped_bad.verify_and_digest().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_from_bytes_labeled() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let sign_bytes = point.to_bytes();
+
+    let point_1 = NewPoint::from_bytes_labeled(&sign_bytes, "point").unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+
+    match NewPoint::from_bytes_labeled(&sign_bytes[..sign_bytes.len() - 1], "point") {
+        Err(KVObjectError::Labeled { label, source }) => {
+            assert_eq!(label, "point");
+            assert_eq!(*source, KVObjectError::BodyLengthMismatch);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_decoder() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point_1 = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point_1.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let point_2 = NewPoint::new(MsgType::QuotaRecycleReceipt, TestPoint { x: 7, y: 9 });
+
+    let mut stream = point_1.to_frame().unwrap();
+    stream.extend_from_slice(&point_2.to_frame().unwrap());
+
+    // 分三次送入，切分点落在第一帧中间和第二帧的长度前缀内
+    let first = point_1.to_frame().unwrap().len();
+    let mut decoder = KVObjectDecoder::<TestPoint>::new();
+    decoder.push(&stream[..first / 2]);
+    assert!(decoder.next().is_none());
+    decoder.push(&stream[first / 2..first + 2]);
+    let decoded_1 = decoder.next().unwrap().unwrap();
+    assert!(decoded_1.verfiy_kvhead().is_ok());
+    assert_eq!(decoded_1.to_bytes(), point_1.to_bytes());
+    assert!(decoder.next().is_none());
+    decoder.push(&stream[first + 2..]);
+    let decoded_2 = decoder.next().unwrap().unwrap();
+    assert_eq!(decoded_2.to_bytes(), point_2.to_bytes());
+    assert!(decoder.next().is_none());
+    assert_eq!(decoder.pending(), 0);
+
+    // 长度前缀超过上限时立即报错，不等待后续数据
+    let mut decoder =
+        KVObjectDecoder::<TestPoint>::with_max_frame_len(point_1.to_bytes().len() - 1);
+    decoder.push(&stream[..FRAME_LEN_LEN]);
+    assert_eq!(
+        decoder.next().unwrap().unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+    assert_eq!(decoder.pending(), 0);
+
+    let mut decoder = KVObjectDecoder::<TestPoint>::new();
+    decoder.push(&u32::MAX.to_le_bytes());
+    assert_eq!(
+        decoder.next().unwrap().unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+
+    // 超过解码器上限的对象无法生成帧
+    let blob = KVObject::<TestBlob>::new(
+        MsgType::Transaction,
+        TestBlob {
+            data: vec![0u8; MAX_FRAME_LEN],
+        },
+    );
+    assert!(blob.to_bytes().len() > MAX_FRAME_LEN);
+    assert_eq!(blob.to_frame().unwrap_err(), KVObjectError::SerializeError);
+}
+
+#[test]
+fn test_verify_components() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let body = TestStamped { timestamp: 1_000 };
+    let mut stamped = KVObject::<TestStamped>::new(MsgType::Transaction, body.clone());
+    stamped.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let cert = stamped.get_cert().clone().unwrap();
+    let sig = stamped.get_signature().clone().unwrap();
+
+    assert!(stamped.verfiy_kvhead().is_ok());
+    assert!(verify_components(&body.to_bytes(), &cert, &sig).is_ok());
+
+    let other = TestStamped { timestamp: 1_001 };
+    let stamped_bad = KVObject::<TestStamped>::from_parts(
+        MsgType::Transaction,
+        Some(cert.clone()),
+        Some(sig.clone()),
+        other.clone(),
+    );
+    assert_eq!(
+        stamped_bad.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+    assert_eq!(
+        verify_components(&other.to_bytes(), &cert, &sig).unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    assert_eq!(
+        verify_components(&body.to_bytes(), &CertificateSm2::default(), &sig).unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+}
+
+#[test]
+fn test_key_alias() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(
+        point.get_key("abscissa").unwrap(),
+        point.get_key("x").unwrap()
+    );
+    assert_eq!(point.key_len("abscissa").unwrap(), 4);
+
+    // 旧名同样受签名覆盖
+    point
+        .set_key("abscissa", &7i32.to_le_bytes().to_vec())
+        .unwrap();
+    assert_eq!(point.get_body().x, 7);
+    assert!(point.is_signature_stale());
+}
+
+#[test]
+fn test_key_lookup_consistent() {
+    let point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+
+    // 旧名在各读取方法中同样生效
+    let value = point.get_key("abscissa").unwrap();
+    let mut buf = Vec::new();
+    point.get_key_into("abscissa", &mut buf).unwrap();
+    assert_eq!(buf, value);
+    assert_eq!(point.key_len("abscissa").unwrap(), value.len());
+    assert_eq!(point.key_byte_len("abscissa").unwrap(), value.len());
+
+    // 未知key和空key在各读取方法中返回相同的错误
+    for key in ["z", "", "  "].iter() {
+        let err = point.get_key(key).unwrap_err();
+        assert_eq!(err, KVObjectError::KeyIndexError);
+        assert_eq!(point.get_key_into(key, &mut buf).unwrap_err(), err);
+        assert_eq!(point.key_len(key).unwrap_err(), err);
+        assert_eq!(point.key_byte_len(key).unwrap_err(), err);
+    }
+}
+
+#[test]
+fn test_serialized_len() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(point.serialized_len(), point.to_bytes().len());
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(point.serialized_len(), point.to_bytes().len());
+
+    // 未实现byte_len的结构按序列化结果计算
+    let prefix = KVObject::<TestPrefix>::from_bytes(&point.to_bytes()).unwrap();
+    assert_eq!(prefix.serialized_len(), prefix.to_bytes().len());
+    let prefix = KVObject::<TestPrefix>::new(MsgType::Transaction, TestPrefix { x: 3 });
+    assert_eq!(prefix.serialized_len(), prefix.to_bytes().len());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestBlob {
+    pub data: Vec<u8>,
+}
+
+impl Bytes for TestBlob {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            data: bytes.to_vec(),
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        self.data.clone()
+    }
+}
+
+impl AttrProxy for TestBlob {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        match key {
+            "data" => Ok(self.data.clone()),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        match key {
+            "data" => self.data = value.clone(),
+            _ => return Err(KVObjectError::KeyIndexError),
+        }
+        Ok(())
+    }
+}
+
+impl KVBody for TestBlob {}
+
+// 仅用于测试的游程编码，每个重复段编码为（次数，字节）
+pub struct RleCodec;
+
+impl BodyCodec for RleCodec {
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut ret = Vec::new();
+        let mut i = 0;
+        while i < data.len() {
+            let mut n = 1;
+            while i + n < data.len() && data[i + n] == data[i] && n < 255 {
+                n += 1;
+            }
+            ret.push(n as u8);
+            ret.push(data[i]);
+            i += n;
+        }
+        ret
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, KVObjectError> {
+        let runs = data.chunks_exact(2);
+        if !runs.remainder().is_empty() {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        let mut ret = Vec::new();
+        for run in runs {
+            ret.resize(ret.len() + run[0] as usize, run[1]);
+        }
+        Ok(ret)
+    }
+}
+
+type RleBlob = KVObjectGeneric<TestBlob, KeyPairSm2, DefaultHasher, RleCodec>;
+
+#[test]
+fn test_compressed_body() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let body = TestBlob {
+        data: vec![0u8; 200],
+    };
+    let mut blob = RleBlob::new(MsgType::Transaction, body.clone());
+    blob.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let plain = KVObject::<TestBlob>::new(MsgType::Transaction, body.clone());
+
+    let bytes = blob.to_bytes();
+    assert!(bytes.len() < plain.to_bytes().len());
+    assert_eq!(bytes[1] & FLAG_COMPRESSED, FLAG_COMPRESSED);
+    assert_eq!(blob.serialized_len(), bytes.len());
+
+    let blob_1 = RleBlob::from_bytes(&bytes).unwrap();
+    assert_eq!(*blob_1.get_body(), body);
+    assert!(blob_1.verfiy_kvhead().is_ok());
+
+    // 签名覆盖压缩后的数据
+    assert!(verify_components(
+        &bytes[HEAD_TOTAL_LEN..],
+        &blob.get_cert().clone().unwrap(),
+        &blob.get_signature().clone().unwrap()
+    )
+    .is_ok());
+
+    // 压缩标志与编码方式不一致时拒绝解析
+    assert_eq!(
+        KVObject::<TestBlob>::from_bytes(&bytes).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+    assert_eq!(
+        RleBlob::from_bytes(&plain.to_bytes()).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[cfg(feature = "deflate")]
+#[test]
+fn test_deflate_body() {
+    use kv_object::kv_object::KVObjectDeflate;
+
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let body = TestBlob {
+        data: vec![7u8; 500],
+    };
+    let mut blob = KVObjectDeflate::<TestBlob>::new(MsgType::Transaction, body.clone());
+    blob.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let bytes = blob.to_bytes();
+    assert!(bytes.len() < HEAD_TOTAL_LEN + body.data.len());
+    let blob_1 = KVObjectDeflate::<TestBlob>::from_bytes(&bytes).unwrap();
+    assert_eq!(*blob_1.get_body(), body);
+    assert!(blob_1.verfiy_kvhead().is_ok());
+}
+
+#[cfg(feature = "deflate")]
+#[test]
+fn test_deflate_body_limit() {
+    use kv_object::codec::{Deflate, MAX_BODY_LEN};
+    use kv_object::kv_object::KVObjectDeflate;
+
+    // 恰好达到上限时可以解压
+    let stream = Deflate::compress(&vec![0u8; MAX_BODY_LEN]);
+    assert_eq!(Deflate::decompress(&stream).unwrap().len(), MAX_BODY_LEN);
+
+    // 解压后超过上限的数据在验签前即被拒绝
+    let stream = Deflate::compress(&vec![0u8; MAX_BODY_LEN + 1]);
+    assert_eq!(
+        Deflate::decompress(&stream).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+
+    let blob = KVObjectDeflate::<TestBlob>::new(
+        MsgType::Transaction,
+        TestBlob {
+            data: vec![0u8; MAX_BODY_LEN + 1],
+        },
+    );
+    assert_eq!(
+        KVObjectDeflate::<TestBlob>::from_bytes(&blob.to_bytes()).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_msg_type_all() {
+    let all = MsgType::all();
+    assert_eq!(all.len(), 6);
+
+    let codes: std::collections::BTreeSet<u8> = all.iter().map(|t| t.as_u8()).collect();
+    let names: std::collections::BTreeSet<&str> = all.iter().map(|t| t.name()).collect();
+    assert_eq!(codes.len(), all.len());
+    assert_eq!(names.len(), all.len());
+
+    for msg_type in all {
+        assert_eq!(MsgType::from_bytes(&[msg_type.as_u8()]).unwrap(), *msg_type);
+    }
+}
+
+// 以任意顺序保存的键值对，字节编码依赖插入顺序
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestMap {
+    pub entries: Vec<(u8, u8)>,
+}
+
+impl Bytes for TestMap {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let pairs = bytes.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        Ok(Self {
+            entries: pairs.map(|pair| (pair[0], pair[1])).collect(),
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        self.entries
+            .iter()
+            .flat_map(|(k, v)| vec![*k, *v])
+            .collect()
+    }
+}
+
+impl AttrProxy for TestMap {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, _key: &str) -> Result<Self::Byte, KVObjectError> {
+        Err(KVObjectError::KeyIndexError)
+    }
+
+    fn set_key(&mut self, _key: &str, _value: &Self::Byte) -> Result<(), KVObjectError> {
+        Err(KVObjectError::KeyIndexError)
+    }
+}
+
+impl KVBody for TestMap {
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut entries = self.entries.clone();
+        entries.sort();
+        TestMap { entries }.to_bytes()
+    }
+}
+
+#[test]
+fn test_canonical_bytes() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let ordered = TestMap {
+        entries: vec![(1, 10), (2, 20), (3, 30)],
+    };
+    let shuffled = TestMap {
+        entries: vec![(3, 30), (1, 10), (2, 20)],
+    };
+    assert_ne!(ordered.to_bytes(), shuffled.to_bytes());
+    assert_eq!(ordered.canonical_bytes(), shuffled.canonical_bytes());
+
+    let mut map = KVObject::<TestMap>::new(MsgType::Transaction, ordered);
+    map.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    // 换一种字节顺序重新编码后签名仍然有效
+    let reencoded = KVObject::<TestMap>::from_parts(
+        MsgType::Transaction,
+        map.get_cert().clone(),
+        map.get_signature().clone(),
+        shuffled,
+    );
+    assert!(reencoded.verfiy_kvhead().is_ok());
+    let reparsed = KVObject::<TestMap>::from_bytes(&reencoded.to_bytes()).unwrap();
+    assert!(reparsed.verfiy_kvhead().is_ok());
+
+    // 写出的字节即被签名的字节：新建的对象写出canonical_bytes，解析的对象原样写出
+    let bytes = map.to_bytes();
+    assert_eq!(reencoded.to_bytes(), bytes);
+    assert_eq!(reparsed.to_bytes(), bytes);
+    assert_eq!(
+        &bytes[bytes.len() - reparsed.get_body().canonical_bytes().len()..],
+        &reparsed.get_body().canonical_bytes()[..]
+    );
+
+    // 收到非规范编码的数据时签名覆盖收到的字节，与签名时的字节不同则验签失败
+    let shuffled_bytes = reencoded.get_body().to_bytes();
+    let mut wire = bytes.clone();
+    let body_start = wire.len() - shuffled_bytes.len();
+    wire[body_start..].copy_from_slice(&shuffled_bytes);
+    let received = KVObject::<TestMap>::from_bytes(&wire).unwrap();
+    assert_eq!(received.to_bytes(), wire);
+    assert_eq!(
+        received.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_issuer() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(point.issuer(), None);
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(
+        &point.issuer().unwrap()[..],
+        &keypair_sm2.get_certificate().to_bytes()[..]
+    );
+}
+
+#[test]
+fn test_type_consistency() {
+    let body = TestStamped { timestamp: 1_000 };
+    let stamped = KVObject::<TestStamped>::new(MsgType::Transaction, body.clone());
+    assert!(stamped.check_type_consistency().is_ok());
+
+    let stamped_bad = KVObject::<TestStamped>::new(MsgType::DigitalCurrency, body);
+    assert_eq!(
+        stamped_bad.check_type_consistency().unwrap_err(),
+        KVObjectError::TypeMismatch
+    );
+
+    // 未声明类型的结构不做检查
+    let point = NewPoint::new(MsgType::DigitalCurrency, TestPoint { x: 3, y: 5 });
+    assert!(point.check_type_consistency().is_ok());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_to_io() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    let mut out = Vec::<u8>::new();
+    point.write_to_io(&mut out).unwrap();
+    assert_eq!(out, point.to_bytes());
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let mut out = Vec::<u8>::new();
+    point.write_to_io(&mut out).unwrap();
+    assert_eq!(out, point.to_bytes());
+}
+
+// 包含嵌套TestPoint的结构
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestNested {
+    pub pos: TestPoint,
+}
+
+impl Bytes for TestNested {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            pos: TestPoint::from_bytes(bytes)?,
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        self.pos.to_bytes()
+    }
+}
+
+impl AttrProxy for TestNested {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        match key {
+            "pos" => Ok(self.pos.to_bytes()),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        match key {
+            "pos" => self.pos = TestPoint::from_bytes(value)?,
+            _ => return Err(KVObjectError::KeyIndexError),
+        }
+        Ok(())
+    }
+}
+
+impl KVBody for TestNested {
+    fn get_path(&self, path: &str) -> Result<Vec<u8>, KVObjectError> {
+        match path.split_once('.') {
+            Some(("pos", rest)) => self.pos.get_path(rest),
+            _ => self.get_key(path),
+        }
+    }
+
+    fn set_path(&mut self, path: &str, value: &[u8]) -> Result<(), KVObjectError> {
+        match path.split_once('.') {
+            Some(("pos", rest)) => self.pos.set_path(rest, value),
+            _ => self.set_key(path, &value.to_vec()),
+        }
+    }
+}
+
+#[test]
+fn test_nested_path() {
+    let mut nested = KVObject::<TestNested>::new(
+        MsgType::Transaction,
+        TestNested {
+            pos: TestPoint { x: 3, y: 5 },
+        },
+    );
+    assert_eq!(
+        nested.get_path("pos.x").unwrap(),
+        3i32.to_le_bytes().to_vec()
+    );
+    assert_eq!(
+        nested.get_path("pos.y").unwrap(),
+        5i32.to_le_bytes().to_vec()
+    );
+    assert_eq!(
+        nested.get_path("pos").unwrap(),
+        TestPoint { x: 3, y: 5 }.to_bytes()
+    );
+
+    nested.set_path("pos.x", &7i32.to_le_bytes()).unwrap();
+    assert_eq!(nested.get_body().pos, TestPoint { x: 7, y: 5 });
+
+    assert_eq!(
+        nested.get_path("pos.z").unwrap_err(),
+        KVObjectError::KeyIndexError
+    );
+    assert_eq!(
+        nested.get_path("other.x").unwrap_err(),
+        KVObjectError::KeyIndexError
+    );
+}
+
+#[test]
+fn test_verify_default_placeholders() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let (msg_type, cert, signature, body) = point.into_parts();
+
+    let default_cert = NewPoint::from_parts(
+        msg_type.clone(),
+        Some(CertificateSm2::default()),
+        signature,
+        body.clone(),
+    );
+    assert_eq!(
+        default_cert.verfiy_kvhead().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+
+    let default_sig = NewPoint::from_parts(msg_type, cert, Some(SignatureSm2::default()), body);
+    assert_eq!(
+        default_sig.verfiy_kvhead().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+}
+
+// TestPoint的新版本，旧数据中缺少的z取默认值0
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestPointV2 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Bytes for TestPointV2 {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let point = TestPoint::from_bytes(&bytes[..bytes.len().min(8)])?;
+        let z = match bytes.len() {
+            8 => 0,
+            12 => i32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+            _ => return Err(KVObjectError::DeSerializeError),
+        };
+        Ok(Self {
+            x: point.x,
+            y: point.y,
+            z,
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        let mut ret = TestPoint {
+            x: self.x,
+            y: self.y,
+        }
+        .to_bytes();
+        ret.extend_from_slice(&self.z.to_le_bytes());
+        ret
+    }
+}
+
+impl AttrProxy for TestPointV2 {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        match key {
+            "x" => Ok(self.x.to_le_bytes().to_vec()),
+            "y" => Ok(self.y.to_le_bytes().to_vec()),
+            "z" => Ok(self.z.to_le_bytes().to_vec()),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn set_key(&mut self, _key: &str, _value: &Self::Byte) -> Result<(), KVObjectError> {
+        Err(KVObjectError::KeyIndexError)
+    }
+}
+
+impl KVBody for TestPointV2 {}
+
+#[test]
+fn test_verify_and_reinterpret() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    // 旧版本写出x和y，签名覆盖整个结构体；以只读取x的宽松结构体解析
+    let data = TestPoint { x: 3, y: 5 }.to_bytes();
+    let mut blob = KVObject::<TestBlob>::new(MsgType::IssueQuotaRequest, TestBlob { data });
+    blob.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let bytes = blob.to_bytes();
+    let prefix = KVObject::<TestPrefix>::from_bytes(&bytes).unwrap();
+
+    let upgraded: TestPointV2 = prefix.verify_and_reinterpret().unwrap();
+    assert_eq!(upgraded, TestPointV2 { x: 3, y: 5, z: 0 });
+
+    // 篡改或追加x之后的数据，宽松解析仍然成功，但重新解析前验签失败
+    let mut tampered = bytes.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    let mut appended = bytes.clone();
+    appended.extend_from_slice(&[9, 0, 0, 0]);
+    for bytes in [tampered, appended].iter() {
+        let prefix = KVObject::<TestPrefix>::from_bytes(bytes).unwrap();
+        assert_eq!(prefix.get_body(), &TestPrefix { x: 3 });
+        assert_eq!(
+            prefix.verify_and_reinterpret::<TestPointV2>().unwrap_err(),
+            KVObjectError::KVHeadVerifyError
+        );
+    }
+
+    let unsigned = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(
+        unsigned
+            .verify_and_reinterpret::<TestPointV2>()
+            .unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+}
+
+#[cfg(feature = "test-support")]
+#[test]
+fn test_fill_kvhead_seeded() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point_1 = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    let mut point_2 = point_1.clone();
+    let mut point_3 = point_1.clone();
+    point_1.fill_kvhead_seeded(&keypair_sm2, [7u8; 32]).unwrap();
+    point_2.fill_kvhead_seeded(&keypair_sm2, [7u8; 32]).unwrap();
+    point_3.fill_kvhead_seeded(&keypair_sm2, [8u8; 32]).unwrap();
+
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(point_1.to_bytes(), point_2.to_bytes());
+    assert_ne!(point_1.to_bytes(), point_3.to_bytes());
+}
+
+#[test]
+fn test_try_clone() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(point.try_clone().unwrap().to_bytes(), point.to_bytes());
+
+    let mut sign_bytes = point.to_bytes();
+    let offset = sign_bytes.len() - 8;
+    sign_bytes[offset] ^= 0x01;
+    let tampered = NewPoint::from_bytes(&sign_bytes).unwrap();
+    assert_eq!(tampered.clone().to_bytes(), sign_bytes);
+    assert_eq!(
+        tampered.try_clone().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_empty_key() {
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    let value = 7i32.to_le_bytes().to_vec();
+
+    for key in ["", "  "].iter() {
+        assert_eq!(
+            point.get_key(key).unwrap_err(),
+            KVObjectError::KeyIndexError
+        );
+        assert_eq!(
+            point.set_key(key, &value).unwrap_err(),
+            KVObjectError::KeyIndexError
+        );
+    }
+    assert_eq!(*point.get_body(), TestPoint { x: 3, y: 5 });
+}
+
+#[test]
+fn test_bytes_eq() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let unsigned = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    let mut signed = unsigned.clone();
+    signed.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let mut resigned = unsigned.clone();
+    resigned.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let parsed = NewPoint::from_bytes(&signed.to_bytes()).unwrap();
+    let parsed_unsigned = NewPoint::from_bytes(&unsigned.to_bytes()).unwrap();
+    let other_type = NewPoint::new(MsgType::Transaction, TestPoint { x: 3, y: 5 });
+    let other_body = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 6 });
+
+    let objs = [
+        unsigned,
+        signed,
+        resigned,
+        parsed,
+        parsed_unsigned,
+        other_type,
+        other_body,
+    ];
+    for a in objs.iter() {
+        for b in objs.iter() {
+            assert_eq!(a.bytes_eq(b), a.to_bytes() == b.to_bytes());
+        }
+    }
+}
+
+#[test]
+fn test_verify_detailed() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(point.verify_detailed(), Ok(()));
+    let (msg_type, cert, signature, body) = point.into_parts();
+
+    let cases = [
+        (None, signature.clone(), VerifyFailure::MissingCert),
+        (cert.clone(), None, VerifyFailure::MissingSignature),
+        (
+            Some(CertificateSm2::default()),
+            signature.clone(),
+            VerifyFailure::DefaultCert,
+        ),
+        (
+            cert.clone(),
+            Some(SignatureSm2::default()),
+            VerifyFailure::DefaultSignature,
+        ),
+    ];
+    for (cert, signature, failure) in cases.iter() {
+        let obj = NewPoint::from_parts(
+            msg_type.clone(),
+            cert.clone(),
+            signature.clone(),
+            body.clone(),
+        );
+        assert_eq!(obj.verify_detailed().unwrap_err(), *failure);
+        assert_eq!(
+            obj.verfiy_kvhead().unwrap_err(),
+            KVObjectError::UnsignedObject
+        );
+    }
+
+    let bad = NewPoint::from_parts(msg_type, cert, signature, TestPoint { x: 4, y: 5 });
+    assert_eq!(
+        bad.verify_detailed().unwrap_err(),
+        VerifyFailure::BadSignature
+    );
+    assert_eq!(
+        bad.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_metadata() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let plain_bytes = point.to_bytes();
+    let plain_id = point.content_id();
+
+    point.set_metadata("route", b"relay-1");
+    point.set_metadata("hops", &[1]);
+    point.set_metadata("hops", &[2]);
+    assert!(point.verfiy_kvhead().is_ok());
+    // 元数据不影响内容标识
+    assert_eq!(point.content_id(), plain_id);
+
+    let bytes = point.to_bytes();
+    assert!(bytes.len() > plain_bytes.len());
+    assert_eq!(point.serialized_len(), bytes.len());
+
+    let point_1 = NewPoint::from_bytes(&bytes).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(point_1.get_metadata("route"), Some(&b"relay-1"[..]));
+    assert_eq!(point_1.get_metadata("hops"), Some(&[2u8][..]));
+    assert_eq!(point_1.get_metadata("missing"), None);
+    assert_eq!(point_1.to_bytes(), bytes);
+    assert_eq!(point_1.content_id(), plain_id);
+
+    // 截断的元数据段无法解析
+    assert_eq!(
+        NewPoint::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_verify_with_id() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point
+        .sign_with_id(&keypair_sm2, b"alice@example.com", &mut rng)
+        .unwrap();
+
+    assert_eq!(point.verify_with_id(b"alice@example.com"), Ok(()));
+    assert_eq!(
+        point.verify_with_id(b"bob@example.com"),
+        Err(KVObjectError::KVHeadVerifyError)
+    );
+    assert_eq!(
+        point.verify_with_id(DEFAULT_USER_ID),
+        Err(KVObjectError::KVHeadVerifyError)
+    );
+    assert_eq!(point.verfiy_kvhead(), Err(KVObjectError::KVHeadVerifyError));
+
+    let bytes = point.to_bytes();
+    let parsed = NewPoint::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.verify_with_id(b"alice@example.com"), Ok(()));
+
+    let unsigned = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(
+        unsigned.verify_with_id(DEFAULT_USER_ID),
+        Err(KVObjectError::UnsignedObject)
+    );
+
+    // 失效的签名和默认占位证书与verify_detailed给出相同的错误
+    let mut stale = parsed.clone();
+    stale.body_mut();
+    let (msg_type, _, signature, body) = parsed.into_parts();
+    let default_cert =
+        NewPoint::from_parts(msg_type, Some(CertificateSm2::default()), signature, body);
+    assert_eq!(
+        stale.verify_with_id(b"alice@example.com"),
+        Err(KVObjectError::KVHeadVerifyError)
+    );
+    assert_eq!(
+        default_cert.verify_with_id(b"alice@example.com"),
+        Err(KVObjectError::UnsignedObject)
+    );
+    for obj in [stale, default_cert].iter() {
+        assert_eq!(
+            obj.verify_with_id(b"alice@example.com"),
+            obj.verify_detailed().map_err(KVObjectError::from)
+        );
+    }
+}
+
+#[test]
+fn test_from_body() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::from(TestPoint { x: 3, y: 5 });
+    assert!(point.get_cert().is_none());
+    assert_eq!(
+        get_msgtpye(&point.to_bytes()).unwrap(),
+        MsgType::IssueQuotaRequest
+    );
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(point.verfiy_kvhead().is_ok());
+    let (msg_type, _, _, body) = point.into_parts();
+    assert_eq!(msg_type, TestPoint::DEFAULT_MSG_TYPE);
+    assert_eq!(body, TestPoint { x: 3, y: 5 });
+}
+
+#[test]
+fn test_serialize_batch() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let objs: Vec<NewPoint> = (0..64)
+        .map(|i| {
+            let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: i, y: 5 });
+            if i % 2 == 0 {
+                point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+            }
+            point
+        })
+        .collect();
+
+    let batch = serialize_batch(&objs);
+    assert_eq!(batch.len(), objs.len());
+    for (bytes, obj) in batch.iter().zip(objs.iter()) {
+        assert_eq!(bytes, &obj.to_bytes());
+    }
+    assert!(serialize_batch(&objs[..0]).is_empty());
+}
+
+#[test]
+fn test_resign_with() {
+    let mut rng = thread_rng();
+    let keypair_a: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_b: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let cert_a = keypair_a.get_certificate();
+    let cert_b = keypair_b.get_certificate();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_a, &mut rng).unwrap();
+    assert_eq!(
+        point.verify_against_any(std::slice::from_ref(&cert_a)),
+        Ok(0)
+    );
+
+    point.resign_with(&keypair_b, &mut rng).unwrap();
+    assert_eq!(point.get_cert(), &Some(cert_b.clone()));
+    assert_eq!(point.verify_against_any(&[cert_b]), Ok(0));
+    assert_eq!(
+        point.verify_against_any(std::slice::from_ref(&cert_a)),
+        Err(KVObjectError::UntrustedIssuer)
+    );
+
+    let (msg_type, _, signature, body) = point.into_parts();
+    let forged = NewPoint::from_parts(msg_type, Some(cert_a), signature, body);
+    assert_eq!(
+        forged.verfiy_kvhead(),
+        Err(KVObjectError::KVHeadVerifyError)
+    );
+}
+
+#[test]
+fn test_body_mut() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.body_mut().x = 4;
+    assert!(!point.is_signature_stale());
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(point.verfiy_kvhead().is_ok());
+
+    point.body_mut().x = 7;
+    assert!(point.is_signature_stale());
+    assert_eq!(point.get_body(), &TestPoint { x: 7, y: 5 });
+    assert_eq!(
+        point.verify_detailed().unwrap_err(),
+        VerifyFailure::StaleSignature
+    );
+    assert_eq!(point.verfiy_kvhead(), Err(KVObjectError::KVHeadVerifyError));
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(!point.is_signature_stale());
+    assert!(point.verfiy_kvhead().is_ok());
+}
+
+#[cfg(feature = "default-hasher-sha3")]
+#[test]
+fn test_default_hasher_sha3() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert!(point.verfiy_kvhead().is_ok());
+
+    let (msg_type, cert, signature, t_obj) = point.into_parts();
+    let as_sha3 = NewPointSha3::from_parts(
+        msg_type.clone(),
+        cert.clone(),
+        signature.clone(),
+        t_obj.clone(),
+    );
+    assert!(as_sha3.verfiy_kvhead().is_ok());
+
+    let as_sm3 = KVObject::<TestPoint, Sm3>::from_parts(msg_type, cert, signature, t_obj);
+    assert_eq!(
+        as_sm3.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+}
+
+#[test]
+fn test_open_with_issuer() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let mut bytes = point.to_bytes();
+
+    let (body, cert) = NewPoint::open_with_issuer(&bytes).unwrap();
+    assert_eq!(body, TestPoint { x: 3, y: 5 });
+    assert_eq!(cert, keypair_sm2.get_certificate());
+
+    bytes[HEAD_TOTAL_LEN] ^= 0x01;
+    assert_eq!(
+        NewPoint::open_with_issuer(&bytes).unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    let unsigned = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(
+        NewPoint::open_with_issuer(&unsigned.to_bytes()).unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+}
+
+#[test]
+fn test_key_width() {
+    let mut point = TestPoint { x: 3, y: 5 };
+    assert_eq!(point.key_width("x"), Ok(4));
+    assert_eq!(point.key_width("z"), Err(KVObjectError::KeyIndexError));
+    assert_eq!(
+        point.set_key("y", &vec![1u8; 8]),
+        Err(KVObjectError::ValueLength {
+            expected: 4,
+            got: 8
+        })
+    );
+    assert_eq!(
+        point.set_key("z", &vec![1u8; 4]),
+        Err(KVObjectError::KeyIndexError)
+    );
+
+    let mut obj = NewPoint::new(MsgType::IssueQuotaRequest, point);
+    assert_eq!(
+        obj.set_key("x", &vec![1u8; 2]),
+        Err(KVObjectError::ValueLength {
+            expected: 4,
+            got: 2
+        })
+    );
+    obj.set_key("x", &7i32.to_le_bytes().to_vec()).unwrap();
+    assert_eq!(obj.get_body(), &TestPoint { x: 7, y: 5 });
+}
+
+#[test]
+fn test_magic() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let file = point.to_bytes_with_magic();
+    assert_eq!(&file[..4], &MAGIC[..]);
+    assert_eq!(&file[4..], &point.to_bytes()[..]);
+
+    let point_1 = NewPoint::from_bytes_with_magic(&file).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+
+    let mut bad = file.clone();
+    bad[0] = b'X';
+    assert_eq!(
+        NewPoint::from_bytes_with_magic(&bad).unwrap_err(),
+        KVObjectError::BadMagic
+    );
+    assert_eq!(
+        NewPoint::from_bytes_with_magic(&file[..3]).unwrap_err(),
+        KVObjectError::BadMagic
+    );
+    // 不带魔数的数据同样被拒绝
+    assert_eq!(
+        NewPoint::from_bytes_with_magic(&point.to_bytes()).unwrap_err(),
+        KVObjectError::BadMagic
+    );
+}
+
+#[test]
+fn test_verify_with_ctx() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let ctx = Sm2Context::new();
+    assert_eq!(point.verify_with_ctx(&ctx), Ok(()));
+
+    let (msg_type, cert, signature, _) = point.clone().into_parts();
+    let parts =
+        |cert, signature, body| NewPoint::from_parts(msg_type.clone(), cert, signature, body);
+    let bad = parts(cert.clone(), signature.clone(), TestPoint { x: 7, y: 5 });
+    let default_cert = parts(
+        Some(CertificateSm2::default()),
+        signature.clone(),
+        TestPoint { x: 3, y: 5 },
+    );
+    let default_sig = parts(
+        cert,
+        Some(SignatureSm2::default()),
+        TestPoint { x: 3, y: 5 },
+    );
+    // 默认证书加上失效的签名，两条路径须给出相同的错误
+    let mut stale_default = default_cert.clone();
+    stale_default.body_mut();
+    let unsigned = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+
+    assert_eq!(
+        bad.verify_with_ctx(&ctx),
+        Err(KVObjectError::KVHeadVerifyError)
+    );
+    assert_eq!(
+        stale_default.verify_with_ctx(&ctx),
+        Err(KVObjectError::KVHeadVerifyError)
+    );
+    assert_eq!(
+        unsigned.verify_with_ctx(&ctx),
+        Err(KVObjectError::UnsignedObject)
+    );
+    for obj in [
+        point,
+        bad,
+        default_cert,
+        default_sig,
+        stale_default,
+        unsigned,
+    ]
+    .iter()
+    {
+        assert_eq!(obj.verify_with_ctx(&ctx), obj.verfiy_kvhead());
+        assert_eq!(
+            obj.verify_with_ctx(&ctx),
+            obj.verify_detailed().map_err(KVObjectError::from)
+        );
+    }
+}
+
+// 使用自定义错误类型的结构体
+#[derive(Debug, PartialEq)]
+pub enum CounterError {
+    WrongLength(usize),
+}
+
+impl From<CounterError> for KVObjectError {
+    fn from(err: CounterError) -> Self {
+        match err {
+            CounterError::WrongLength(got) => KVObjectError::ValueLength { expected: 4, got },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestCounter {
+    pub n: u32,
+}
+
+impl Bytes for TestCounter {
+    type BytesType = Vec<u8>;
+
+    type Error = CounterError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != 4 {
+            return Err(CounterError::WrongLength(bytes.len()));
+        }
+        let mut n = [0u8; 4];
+        n.clone_from_slice(bytes);
+        Ok(Self {
+            n: u32::from_le_bytes(n),
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        self.n.to_le_bytes().to_vec()
+    }
+}
+
+impl AttrProxy for TestCounter {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        match key {
+            "n" => Ok(self.n.to_le_bytes().to_vec()),
+            _ => Err(KVObjectError::KeyIndexError),
+        }
+    }
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        match key {
+            "n" => *self = TestCounter::from_bytes(value)?,
+            _ => return Err(KVObjectError::KeyIndexError),
+        }
+        Ok(())
+    }
+}
+
+impl KVBody for TestCounter {}
+
+#[test]
+fn test_custom_body_error() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut counter = KVObject::<TestCounter>::new(MsgType::Transaction, TestCounter { n: 42 });
+    counter.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let bytes = counter.to_bytes();
+
+    let counter_1 = KVObject::<TestCounter>::from_bytes(&bytes).unwrap();
+    assert!(counter_1.verfiy_kvhead().is_ok());
+    assert_eq!(counter_1.get_body(), &TestCounter { n: 42 });
+
+    let mut long = bytes.clone();
+    long.push(0);
+    assert_eq!(
+        KVObject::<TestCounter>::from_bytes(&long).unwrap_err(),
+        KVObjectError::ValueLength {
+            expected: 4,
+            got: 5
+        }
+    );
+}
+
+#[test]
+fn test_count_framed() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut stream = Vec::new();
+    for x in 0..3 {
+        let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x, y: 5 });
+        if x != 1 {
+            point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+        }
+        stream.extend_from_slice(&point.to_frame().unwrap());
+    }
+    assert_eq!(count_framed(&stream), Ok(3));
+    assert_eq!(count_framed(&[]), Ok(0));
+
+    assert_eq!(
+        count_framed(&stream[..stream.len() - 1]),
+        Err(KVObjectError::DeSerializeError)
+    );
+    assert_eq!(
+        count_framed(&stream[..2]),
+        Err(KVObjectError::DeSerializeError)
+    );
+
+    // 长度前缀超过MAX_FRAME_LEN时即使数据完整也被拒绝
+    let mut oversized = vec![0u8; FRAME_LEN_LEN + MAX_FRAME_LEN + 1];
+    oversized[..FRAME_LEN_LEN].clone_from_slice(&((MAX_FRAME_LEN + 1) as u32).to_le_bytes());
+    assert_eq!(
+        count_framed(&oversized),
+        Err(KVObjectError::DeSerializeError)
+    );
+}
+
+#[test]
+fn test_from_bytes_fixed() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let bytes = point.to_bytes();
+    assert_eq!(bytes.len(), HEAD_TOTAL_LEN + 8);
+
+    let point_1 = NewPoint::from_bytes_fixed(&bytes).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+
+    assert_eq!(
+        NewPoint::from_bytes_fixed(&bytes[..bytes.len() - 1]).unwrap_err(),
+        KVObjectError::LengthMismatch
+    );
+    let mut padded = bytes.clone();
+    padded.push(0);
+    assert_eq!(
+        NewPoint::from_bytes_fixed(&padded).unwrap_err(),
+        KVObjectError::LengthMismatch
+    );
+
+    point.set_metadata("hop", b"node-1");
+    assert_eq!(
+        NewPoint::from_bytes_fixed(&point.to_bytes()).unwrap_err(),
+        KVObjectError::LengthMismatch
+    );
+}
+
+#[test]
+fn test_cert_chain_builder() {
+    let mut rng = thread_rng();
+    let root: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let intermediate: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let leaf: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let other: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let chain = CertChainBuilder::new()
+        .push_signed_by(&intermediate, &root, &mut rng)
+        .unwrap()
+        .push_signed_by(&leaf, &intermediate, &mut rng)
+        .unwrap()
+        .build();
+    assert_eq!(chain.links().len(), 2);
+    assert_eq!(
+        chain.verify(&root.get_certificate()),
+        Ok(&leaf.get_certificate())
+    );
+    assert_eq!(
+        chain.verify(&other.get_certificate()),
+        Err(KVObjectError::KVHeadVerifyError)
+    );
+
+    // 上级不是上一级证书的持有者
+    assert_eq!(
+        CertChainBuilder::new()
+            .push_signed_by(&intermediate, &root, &mut rng)
+            .unwrap()
+            .push_signed_by(&leaf, &other, &mut rng)
+            .unwrap_err(),
+        KVObjectError::UntrustedIssuer
+    );
+
+    let empty = CertChainBuilder::new().build();
+    assert_eq!(
+        empty.verify(&root.get_certificate()),
+        Err(KVObjectError::UnsignedObject)
+    );
+}
+
+#[test]
+fn test_json_envelope() {
+    use dislog_hal::Bytes as _;
+    use hex::ToHex;
+
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let envelope = point.to_json_envelope();
+    let cert_hex = keypair_sm2
+        .get_certificate()
+        .to_bytes()
+        .as_ref()
+        .encode_hex_upper::<String>();
+    assert!(envelope.starts_with(r#"{"type":"IssueQuotaRequest","cert":""#));
+    assert!(envelope.contains(&cert_hex));
+    // x = 3, y = 5，小端序
+    assert!(envelope.ends_with(r#""body":"AwAAAAUAAAA="}"#));
+
+    let point_1 = NewPoint::from_json_envelope(&envelope).unwrap();
+    assert!(point_1.verfiy_kvhead().is_ok());
+    assert_eq!(point_1.get_body(), &TestPoint { x: 3, y: 5 });
+    assert_eq!(point_1.to_bytes(), point.to_bytes());
+
+    let unsigned = NewPoint::new(MsgType::Transaction, TestPoint { x: 1, y: 2 });
+    let envelope = unsigned.to_json_envelope();
+    assert!(envelope.contains(r#""cert":null,"signature":null"#));
+    let unsigned_1 = NewPoint::from_json_envelope(&envelope).unwrap();
+    assert!(unsigned_1.get_cert().is_none());
+    assert_eq!(unsigned_1.to_bytes(), unsigned.to_bytes());
+
+    assert_eq!(
+        NewPoint::from_json_envelope(&envelope.replace("Transaction", "Unknown")).unwrap_err(),
+        KVObjectError::FindTypeError
+    );
+    assert!(NewPoint::from_json_envelope(r#"{"type":"Transaction"}"#).is_err());
+
+    // body的填充不完整、末尾未用位不为零时拒绝
+    let signed = point.to_json_envelope();
+    for body in [
+        r#""AwAAAAUAAAA""#,
+        r#""AwAAAAUAAAA==""#,
+        r#""AwAAAAUAAAB=""#,
+    ]
+    .iter()
+    {
+        let bad = signed.replace(r#""AwAAAAUAAAA=""#, body);
+        assert_eq!(
+            NewPoint::from_json_envelope(&bad).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+    }
+
+    // 重复的字段和对象之后的多余数据
+    let duplicated = signed.replacen('{', r#"{"body":"AwAAAAUAAAA=","#, 1);
+    assert_eq!(
+        NewPoint::from_json_envelope(&duplicated).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+    for trailing in ["x", "{}", ","].iter() {
+        assert_eq!(
+            NewPoint::from_json_envelope(&(signed.clone() + trailing)).unwrap_err(),
+            KVObjectError::DeSerializeError
+        );
+    }
+}
+
+#[test]
+fn test_length_commitment() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let blob = TestBlob {
+        data: b"quota:100".to_vec(),
+    };
+    let mut obj = KVObject::<TestBlob>::new(MsgType::Transaction, blob.clone());
+    obj.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let bytes = obj.to_bytes();
+    let cert = obj.get_cert().clone().unwrap();
+    let sig = obj.get_signature().clone().unwrap();
+
+    // 签名原文为长度前缀和数据，verify_components只需提供数据
+    assert!(verify_components(&blob.data, &cert, &sig).is_ok());
+
+    // TestBlob的解析接受任意长度，截断或追加的数据仍能解析但无法通过验签
+    let mut appended = bytes.clone();
+    appended.push(b'0');
+    let appended = KVObject::<TestBlob>::from_bytes(&appended).unwrap();
+    assert_eq!(appended.get_body().data, b"quota:1000".to_vec());
+    assert_eq!(
+        appended.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    let truncated = KVObject::<TestBlob>::from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+    assert_eq!(
+        truncated.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    // TestPrefix只读取前4字节，追加的数据被解析器忽略，但签名覆盖收到的原始数据
+    let mut prefix = KVObject::<TestPrefix>::new(MsgType::Transaction, TestPrefix { x: 3 });
+    prefix.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let mut lenient = prefix.to_bytes();
+    lenient.extend_from_slice(&[0xaa; 8]);
+    let lenient = KVObject::<TestPrefix>::from_bytes(&lenient).unwrap();
+    assert_eq!(lenient.get_body(), prefix.get_body());
+    assert_eq!(
+        lenient.verfiy_kvhead().unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+    assert!(KVObjectRef::parse_ref(&lenient.to_bytes())
+        .unwrap()
+        .verify::<TestPrefix>()
+        .is_err());
+
+    // 其他版本的数据被拒绝
+    let mut legacy = bytes.clone();
+    legacy[1] &= 0x0f;
+    assert_eq!(
+        KVObject::<TestBlob>::from_bytes(&legacy).unwrap_err(),
+        KVObjectError::DeSerializeError
+    );
+}
+
+#[test]
+fn test_body_bytes() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    assert_eq!(point.body_bytes(), point.get_body().to_bytes());
+
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let bytes = point.to_bytes();
+    let point_1 = NewPoint::from_bytes(&bytes).unwrap();
+    assert_eq!(point_1.body_bytes(), point_1.get_body().to_bytes());
+    assert_eq!(&point_1.body_bytes()[..], &bytes[HEAD_TOTAL_LEN..]);
+}
+
+#[test]
+fn test_msg_type_serde() {
+    let json = serde_json::to_string(&MsgType::DigitalCurrency).unwrap();
+    assert_eq!(json, "\"DigitalCurrency\"");
+    let parsed: MsgType = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, MsgType::DigitalCurrency);
+    assert!(serde_json::from_str::<MsgType>("\"Unknown\"").is_err());
+
+    let binary = bincode::serialize(&MsgType::DigitalCurrency).unwrap();
+    assert_eq!(binary, vec![MsgType::DigitalCurrency.as_u8()]);
+    let parsed: MsgType = bincode::deserialize(&binary).unwrap();
+    assert_eq!(parsed, MsgType::DigitalCurrency);
+    assert!(bincode::deserialize::<MsgType>(&[0xff]).is_err());
+}
+
+// 按写入顺序追加键值对，key为单个字符，记录追加时的重新分配次数
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TestAttrLog {
+    pub entries: Vec<(u8, u8)>,
+    #[serde(skip)]
+    pub reallocs: usize,
+}
+
+impl Bytes for TestAttrLog {
+    type BytesType = Vec<u8>;
+
+    type Error = KVObjectError;
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let pairs = bytes.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return Err(KVObjectError::DeSerializeError);
+        }
+        Ok(Self {
+            entries: pairs.map(|pair| (pair[0], pair[1])).collect(),
+            reallocs: 0,
+        })
+    }
+
+    fn to_bytes(&self) -> Self::BytesType {
+        self.entries
+            .iter()
+            .flat_map(|(k, v)| vec![*k, *v])
+            .collect()
+    }
+}
+
+impl AttrProxy for TestAttrLog {
+    type Byte = Vec<u8>;
+
+    fn get_key(&self, key: &str) -> Result<Self::Byte, KVObjectError> {
+        let key = *key.as_bytes().first().ok_or(KVObjectError::KeyIndexError)?;
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| vec![*v])
+            .ok_or(KVObjectError::KeyIndexError)
+    }
+
+    fn set_key(&mut self, key: &str, value: &Self::Byte) -> Result<(), KVObjectError> {
+        if key.len() != 1 {
+            return Err(KVObjectError::KeyIndexError);
+        }
+        if value.len() != 1 {
+            return Err(KVObjectError::ValueLength {
+                expected: 1,
+                got: value.len(),
+            });
+        }
+        let capacity = self.entries.capacity();
+        self.entries.push((key.as_bytes()[0], value[0]));
+        if self.entries.capacity() != capacity {
+            self.reallocs += 1;
+        }
+        Ok(())
+    }
+
+    fn reserve_attrs(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+    }
+}
+
+impl KVBody for TestAttrLog {}
+
+#[test]
+fn test_set_keys_reserve() {
+    let pairs: Vec<(&str, Vec<u8>)> = ["a", "b", "c", "d", "e", "f", "g", "h", "i"]
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (*key, vec![i as u8]))
+        .collect();
+
+    let mut log = TestAttrLog {
+        entries: Vec::new(),
+        reallocs: 0,
+    };
+    log.set_keys(&pairs).unwrap();
+    assert_eq!(log.reallocs, 0);
+    assert!(log.entries.capacity() >= pairs.len());
+    assert_eq!(log.get_key("e").unwrap(), vec![4]);
+
+    // 逐个写入时会多次扩容
+    let mut one_by_one = TestAttrLog {
+        entries: Vec::new(),
+        reallocs: 0,
+    };
+    for (key, value) in pairs.iter() {
+        one_by_one.set_key(key, value).unwrap();
+    }
+    assert!(one_by_one.reallocs > 0);
+    assert_eq!(one_by_one.entries, log.entries);
+
+    // KVObject转发预留请求，批量写入同样不扩容
+    let body = TestAttrLog {
+        entries: Vec::new(),
+        reallocs: 0,
+    };
+    let mut obj = KVObject::<TestAttrLog>::new(MsgType::Transaction, body);
+    obj.set_keys(&pairs).unwrap();
+    assert_eq!(obj.get_body().reallocs, 0);
+
+    // 遇到错误立即返回
+    let mut log = TestAttrLog {
+        entries: Vec::new(),
+        reallocs: 0,
+    };
+    let bad = vec![("a", vec![0]), ("", vec![1]), ("c", vec![2])];
+    assert_eq!(
+        log.set_keys(&bad).unwrap_err(),
+        KVObjectError::KeyIndexError
+    );
+    assert_eq!(log.entries, vec![(b'a', 0)]);
+}
+
+#[test]
+fn test_verify_message() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let cert = keypair_sm2.get_certificate();
+
+    // 服务端下发随机挑战值，客户端签名后由服务端验证
+    let mut nonce = [0u8; 32];
+    rng.fill_bytes(&mut nonce);
+    let response = keypair_sm2.sign::<Sm3, _>(&nonce, &mut rng).unwrap();
+    assert!(cert.verify_message::<Sm3>(&nonce, &response));
+
+    let mut other = nonce;
+    other[0] ^= 0x01;
+    assert!(!cert.verify_message::<Sm3>(&other, &response));
+    let stranger: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    assert!(!stranger
+        .get_certificate()
+        .verify_message::<Sm3>(&nonce, &response));
+}
+
+#[test]
+fn test_parse_ref() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: 5, y: -6 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let bytes = point.to_bytes();
+
+    let view = KVObjectRef::parse_ref(&bytes).unwrap();
+    assert_eq!(*view.msg_type(), MsgType::Transaction);
+    assert_eq!(view.body_slice(), &bytes[HEAD_TOTAL_LEN..]);
+    assert_eq!(
+        view.cert().unwrap(),
+        keypair_sm2.get_certificate().to_bytes().as_ref()
+    );
+    view.verify::<TestPoint>().unwrap();
+
+    let mut tampered = bytes.clone();
+    let last = tampered.len() - 8;
+    tampered[last] ^= 0x01;
+    assert_eq!(
+        KVObjectRef::parse_ref(&tampered)
+            .unwrap()
+            .verify::<TestPoint>()
+            .unwrap_err(),
+        KVObjectError::KVHeadVerifyError
+    );
+
+    // 元数据不计入数据段
+    point.set_metadata("trace", b"abc");
+    let bytes = point.to_bytes();
+    let view = KVObjectRef::parse_ref(&bytes).unwrap();
+    assert_eq!(view.body_slice(), &point.body_bytes()[..]);
+    view.verify::<TestPoint>().unwrap();
+
+    let unsigned = KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: 1, y: 2 });
+    let bytes = unsigned.to_bytes();
+    let view = KVObjectRef::parse_ref(&bytes).unwrap();
+    assert!(view.cert().is_none());
+    assert_eq!(
+        view.verify::<TestPoint>().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+    assert!(KVObjectRef::parse_ref(&bytes[..HEAD_TOTAL_LEN - 1]).is_err());
+}
+
+#[test]
+fn test_keyid() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let other: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut registry = KeyRegistry::new();
+    let id = registry.insert(keypair_sm2.get_certificate());
+    registry.insert(other.get_certificate());
+    assert_eq!(registry.len(), 2);
+    assert_eq!(id, key_id(&keypair_sm2.get_certificate()));
+
+    let mut point = KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: 5, y: 6 });
+    assert_eq!(
+        point.to_bytes_keyid().unwrap_err(),
+        KVObjectError::UnsignedObject
+    );
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let bytes = point.to_bytes_keyid().unwrap();
+    assert_eq!(&bytes[..KEY_ID_LEN], &id[..]);
+    assert_eq!(bytes.len(), point.to_bytes().len() - CERT_LEN + KEY_ID_LEN);
+
+    let point_1 = KVObject::<TestPoint>::from_bytes_keyid(&bytes, &registry).unwrap();
+    point_1.verfiy_kvhead().unwrap();
+    assert_eq!(point_1.get_body(), point.get_body());
+    assert_eq!(*point_1.get_cert(), Some(keypair_sm2.get_certificate()));
+
+    let mut unknown = KeyRegistry::new();
+    unknown.insert(other.get_certificate());
+    assert_eq!(
+        KVObject::<TestPoint>::from_bytes_keyid(&bytes, &unknown).unwrap_err(),
+        KVObjectError::UntrustedIssuer
+    );
+    assert!(KVObject::<TestPoint>::from_bytes_keyid(&bytes[..4], &registry).is_err());
+}
+
+#[test]
+fn test_downgrade_to_unsigned() {
+    let mut rng = thread_rng();
+    let keypair_a: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_b: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_a, &mut rng).unwrap();
+    point.body_mut().y = 6;
+    assert!(point.is_signature_stale());
+
+    point.downgrade_to_unsigned();
+    assert!(point.get_cert().is_none());
+    assert!(point.get_signature().is_none());
+    assert!(!point.is_signature_stale());
+    assert_eq!(point.verfiy_kvhead(), Err(KVObjectError::UnsignedObject));
+    assert_eq!(point.to_bytes()[1], FORMAT_VERSION << VERSION_SHIFT);
+
+    point.fill_kvhead(&keypair_b, &mut rng).unwrap();
+    point.verfiy_kvhead().unwrap();
+    assert_eq!(*point.get_cert(), Some(keypair_b.get_certificate()));
+}
+
+#[test]
+fn test_merkle_root() {
+    use hex::ToHex;
+
+    // 未签名对象写出默认头部，内容固定，Merkle根可以固定为字面值
+    let objs: Vec<KVObject<TestPoint>> = (0..3)
+        .map(|i| KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: i, y: i }))
+        .collect();
+    let node = |left: &[u8; 32], right: &[u8; 32]| {
+        let mut hasher = Sm3::default();
+        hasher.update(&left[..]);
+        hasher.update(&right[..]);
+        hasher.finalize()
+    };
+    let (a, b, c) = (
+        objs[0].content_id(),
+        objs[1].content_id(),
+        objs[2].content_id(),
+    );
+
+    // 第三个叶子复制后与自身配对
+    let root = merkle_root::<TestPoint, Sm3>(&objs);
+    assert_eq!(root, node(&node(&a, &b), &node(&c, &c)));
+    assert_eq!(
+        root.encode_hex::<String>(),
+        "e04e46ffe660822e2a8f9f6ad7a7910f444af3a6f328dac10bc5e758cfd95e1c"
+    );
+    assert_eq!(merkle_root::<TestPoint, Sm3>(&objs[..2]), node(&a, &b));
+    assert_eq!(merkle_root::<TestPoint, Sm3>(&objs[..1]), a);
+    assert_eq!(merkle_root::<TestPoint, Sm3>(&[]), [0u8; 32]);
+
+    // 复制最后一个叶子的副作用：[a, b, c]与[a, b, c, c]的根相同
+    let mut padded = objs.clone();
+    padded.push(objs[2].clone());
+    assert_eq!(merkle_root::<TestPoint, Sm3>(&padded), root);
+}
+
+#[test]
+fn test_key_byte_len() {
+    let point = TestPoint { x: 3, y: -5 };
+    for key in point.list_keys() {
+        assert_eq!(
+            point.key_byte_len(key).unwrap(),
+            point.get_key(key).unwrap().len()
+        );
+    }
+    assert_eq!(
+        point.key_byte_len("z").unwrap_err(),
+        KVObjectError::KeyIndexError
+    );
+
+    // 未覆盖时读取值后取长度
+    let blob = TestBlob {
+        data: vec![1, 2, 3],
+    };
+    assert_eq!(blob.key_byte_len("data").unwrap(), 3);
+    assert!(blob.key_byte_len("other").is_err());
+
+    let obj = KVObject::<TestPoint>::new(MsgType::Transaction, point);
+    assert_eq!(obj.key_byte_len("x").unwrap(), 4);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn test_bincode() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: 3, y: -5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+
+    let bytes = point.to_bincode().unwrap();
+    let point_1 = KVObject::<TestPoint>::from_bincode(&bytes).unwrap();
+    point_1.verfiy_kvhead().unwrap();
+    assert_eq!(point_1.get_body(), point.get_body());
+    assert_eq!(point_1.get_cert(), point.get_cert());
+    assert!(bytes.len() < serde_json::to_string(&point).unwrap().len());
+
+    assert!(KVObject::<TestPoint>::from_bincode(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn test_resign_all() {
+    let mut rng = thread_rng();
+    let keypair_a: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let keypair_b: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let cert_a = keypair_a.get_certificate();
+    let cert_b = keypair_b.get_certificate();
+
+    let mut objs: Vec<KVObject<TestPoint>> = (0..3)
+        .map(|i| {
+            let mut obj =
+                KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: i, y: i });
+            obj.fill_kvhead(&keypair_a, &mut rng).unwrap();
+            obj
+        })
+        .collect();
+
+    resign_all(&mut objs, &keypair_b, &mut rng).unwrap();
+    for obj in objs.iter() {
+        assert_eq!(obj.verify_against_any(std::slice::from_ref(&cert_b)), Ok(0));
+        assert_eq!(
+            obj.verify_against_any(std::slice::from_ref(&cert_a)),
+            Err(KVObjectError::UntrustedIssuer)
+        );
+    }
+}
+
+#[test]
+fn test_open_validated() {
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let stranger: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+    let cert = keypair_sm2.get_certificate();
+    let non_negative = |p: &TestPoint| p.x >= 0 && p.y >= 0;
+
+    let mut point = KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let body =
+        KVObject::<TestPoint>::open_validated(&point.to_bytes(), &cert, non_negative).unwrap();
+    assert_eq!(body, TestPoint { x: 3, y: 5 });
+
+    let mut negative = KVObject::<TestPoint>::new(MsgType::Transaction, TestPoint { x: 3, y: -5 });
+    negative.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    assert_eq!(
+        KVObject::<TestPoint>::open_validated(&negative.to_bytes(), &cert, non_negative)
+            .unwrap_err(),
+        KVObjectError::BodyRejected
+    );
+
+    // 先验签，签发者不受信任时不调用pred
+    assert_eq!(
+        KVObject::<TestPoint>::open_validated(
+            &point.to_bytes(),
+            &stranger.get_certificate(),
+            |_| panic!("predicate called before verification"),
+        )
+        .unwrap_err(),
+        KVObjectError::UntrustedIssuer
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn test_parse_metrics() {
+    use kv_object::metrics::{last_parse_metrics, take_parse_metrics};
+
+    let mut rng = thread_rng();
+    let keypair_sm2: KeyPairSm2 = KeyPairSm2::generate(&mut rng).unwrap();
+
+    let mut point = NewPoint::new(MsgType::IssueQuotaRequest, TestPoint { x: 3, y: 5 });
+    point.fill_kvhead(&keypair_sm2, &mut rng).unwrap();
+    let sign_bytes = point.to_bytes();
+
+    take_parse_metrics();
+    assert_eq!(take_parse_metrics(), None);
+
+    // 成功解析后记录耗时，取出后清空
+    NewPoint::from_bytes(&sign_bytes).unwrap();
+    let last = last_parse_metrics();
+    assert_eq!(take_parse_metrics(), Some(last));
+    assert_eq!(take_parse_metrics(), None);
+    assert_eq!(last_parse_metrics(), Default::default());
+
+    // 解析失败时不记录
+    NewPoint::from_bytes(&sign_bytes[..sign_bytes.len() - 1]).unwrap_err();
+    assert_eq!(take_parse_metrics(), None);
+}